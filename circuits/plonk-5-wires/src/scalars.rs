@@ -5,10 +5,16 @@ This source file implements Plonk prover polynomial evaluations primitive.
 *****************************************************************************************************************/
 
 pub use super::wires::COLUMNS;
+use algebra::bytes::{FromBytes, ToBytes};
 use algebra::{FftField, Field};
 use array_init::array_init;
 use ff_fft::DensePolynomial;
-use oracle::{sponge::ScalarChallenge, utils::PolyUtils};
+use oracle::{
+    serialization::{read_fields, write_fields},
+    sponge::ScalarChallenge,
+    utils::PolyUtils,
+};
+use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Clone)]
 pub struct ProofEvaluations<Fs> {
@@ -31,6 +37,46 @@ impl<F: FftField> ProofEvaluations<Vec<F>> {
     }
 }
 
+impl<F: FftField> ToBytes for ProofEvaluations<Vec<F>> {
+    #[inline]
+    fn write<W: Write>(&self, mut w: W) -> IoResult<()> {
+        for evals in self.w.iter() {
+            write_fields(evals, &mut w)?;
+        }
+        write_fields(&self.z, &mut w)?;
+        write_fields(&self.t, &mut w)?;
+        write_fields(&self.f, &mut w)?;
+        for evals in self.s.iter() {
+            write_fields(evals, &mut w)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: FftField> FromBytes for ProofEvaluations<Vec<F>> {
+    #[inline]
+    fn read<R: Read>(mut r: R) -> IoResult<Self> {
+        let mut w = vec![];
+        for _ in 0..COLUMNS {
+            w.push(read_fields(&mut r)?);
+        }
+        let z = read_fields(&mut r)?;
+        let t = read_fields(&mut r)?;
+        let f = read_fields(&mut r)?;
+        let mut s = vec![];
+        for _ in 0..COLUMNS - 1 {
+            s.push(read_fields(&mut r)?);
+        }
+        Ok(ProofEvaluations {
+            w: array_init(|i| w[i].clone()),
+            z,
+            t,
+            f,
+            s: array_init(|i| s[i].clone()),
+        })
+    }
+}
+
 #[derive(Clone)]
 #[cfg_attr(feature = "ocaml_types", derive(ocaml::ToValue, ocaml::FromValue))]
 pub struct CamlProofEvaluations<Fs> {
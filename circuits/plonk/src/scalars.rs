@@ -4,9 +4,15 @@ This source file implements Plonk prover polynomial evaluations primitive.
 
 *****************************************************************************************************************/
 
+use algebra::bytes::{FromBytes, ToBytes};
 use algebra::{FftField, Field};
 use ff_fft::DensePolynomial;
-use oracle::{sponge::ScalarChallenge, utils::PolyUtils};
+use oracle::{
+    serialization::{read_fields, write_fields},
+    sponge::ScalarChallenge,
+    utils::PolyUtils,
+};
+use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Clone)]
 #[cfg_attr(feature = "ocaml_types", derive(ocaml::IntoValue, ocaml::FromValue))]
@@ -36,6 +42,43 @@ impl<F: FftField> ProofEvaluations<Vec<F>> {
     }
 }
 
+impl<F: FftField> ToBytes for ProofEvaluations<Vec<F>> {
+    #[inline]
+    fn write<W: Write>(&self, mut w: W) -> IoResult<()> {
+        for evals in [
+            &self.l,
+            &self.r,
+            &self.o,
+            &self.z,
+            &self.t,
+            &self.f,
+            &self.sigma1,
+            &self.sigma2,
+        ]
+        .iter()
+        {
+            write_fields(evals, &mut w)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: FftField> FromBytes for ProofEvaluations<Vec<F>> {
+    #[inline]
+    fn read<R: Read>(mut r: R) -> IoResult<Self> {
+        Ok(ProofEvaluations {
+            l: read_fields(&mut r)?,
+            r: read_fields(&mut r)?,
+            o: read_fields(&mut r)?,
+            z: read_fields(&mut r)?,
+            t: read_fields(&mut r)?,
+            f: read_fields(&mut r)?,
+            sigma1: read_fields(&mut r)?,
+            sigma2: read_fields(&mut r)?,
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "ocaml_types", derive(ocaml::IntoValue, ocaml::FromValue))]
 pub struct RandomOracles<F: Field>
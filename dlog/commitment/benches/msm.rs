@@ -0,0 +1,60 @@
+/*****************************************************************************************************************
+
+This source file benchmarks the multi-scalar multiplication using the endomorphism of the pasta
+curves against the zexe variable base multi-scalar multiplication
+
+    cargo bench -p commitment_dlog --bench msm
+
+*****************************************************************************************************************/
+
+use algebra::{AffineCurve, PrimeField, ProjectiveCurve, UniformRand, VariableBaseMSM};
+use colored::Colorize;
+use commitment_dlog::{commitment::CommitmentCurve, srs::endos};
+use mina_curves::pasta::{pallas::Affine as Pallas, vesta::Affine as Vesta};
+use std::time::Instant;
+
+fn bench<G: CommitmentCurve>(name: &str, log_sizes: &[usize])
+where
+    G::BaseField: PrimeField,
+{
+    let rng = &mut rand::thread_rng();
+    let (endo_q, endo_r) = endos::<G>();
+    let glv = G::glv(endo_q, endo_r);
+
+    for log_size in log_sizes.iter() {
+        let n = 1 << log_size;
+        let mut bases = (0..n)
+            .map(|_| G::prime_subgroup_generator().mul(G::ScalarField::rand(rng)))
+            .collect::<Vec<_>>();
+        G::Projective::batch_normalization(bases.as_mut_slice());
+        let bases = bases.iter().map(|g| g.into_affine()).collect::<Vec<_>>();
+        let scalars = (0..n)
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+
+        let start = Instant::now();
+        let repr = scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>();
+        let expected = VariableBaseMSM::multi_scalar_mul(&bases, &repr);
+        let zexe = start.elapsed();
+
+        let start = Instant::now();
+        let res = G::msm(&glv, &bases, &scalars);
+        let endo = start.elapsed();
+
+        assert_eq!(res, expected);
+        println!(
+            "{} 2^{}: {} {:?}, {} {:?}",
+            name,
+            log_size,
+            "zexe".yellow(),
+            zexe,
+            "glv".yellow(),
+            endo
+        );
+    }
+}
+
+fn main() {
+    bench::<Vesta>("vesta", &[8, 10, 12, 14, 16]);
+    bench::<Pallas>("pallas", &[8, 10, 12, 14, 16]);
+}
@@ -5,7 +5,7 @@ use algebra::{
 use itertools::Itertools;
 use rayon::prelude::*;
 
-fn add_pairs_in_place<P: SWModelParameters>(p: &mut Vec<SWJAffine<P>>) {
+pub(crate) fn add_pairs_in_place<P: SWModelParameters>(p: &mut Vec<SWJAffine<P>>) {
     let len = if p.len() % 2 == 0 {
         p.len()
     } else {
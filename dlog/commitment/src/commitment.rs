@@ -7,22 +7,30 @@ The folowing functionality is implemented
 2. Open polynomial commitment batch at the given evaluation point and scaling factor scalar
     producing the batched opening proof
 3. Verify batch of batched opening proofs
+4. Open polynomial commitment batch at the sets of evaluation points of the polynomials
+    and verify such openings
 
 *****************************************************************************************************************/
 
+use crate::glv::Glv;
+use crate::segments::{commit_segments, commit_segments_stream};
+use crate::serialization::{read_len, read_point, read_points, write_point, write_points};
 use crate::srs::SRS;
 pub use crate::CommitmentField;
 use algebra::{
     curves::models::short_weierstrass_jacobian::GroupAffine as SWJAffine, AffineCurve, Field,
-    FpParameters, One, PrimeField, ProjectiveCurve, SWModelParameters, SquareRootField,
-    UniformRand, VariableBaseMSM, Zero,
+    FpParameters, FromBytes, One, PrimeField, ProjectiveCurve, SWModelParameters, SquareRootField,
+    ToBytes, UniformRand, VariableBaseMSM, Zero,
 };
-use ff_fft::DensePolynomial;
+use ff_fft::{DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain as D};
 use groupmap::{BWParameters, GroupMap};
-use oracle::{sponge::ScalarChallenge, FqSponge};
+use oracle::{rndoracle::FailedCheck, sponge::ScalarChallenge, FqSponge};
 use rand_core::RngCore;
 use rayon::prelude::*;
+use std::cmp::{max, min};
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
 use std::iter::Iterator;
+use std::ops::{Add, Mul, Neg, Sub};
 
 type Fr<G> = <G as AffineCurve>::ScalarField;
 type Fq<G> = <G as AffineCurve>::BaseField;
@@ -46,6 +54,33 @@ impl<A: Copy> PolyComm<A> {
     }
 }
 
+impl<G: AffineCurve> ToBytes for PolyComm<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut w: W) -> IoResult<()> {
+        write_points(&self.unshifted, &mut w)?;
+        match &self.shifted {
+            None => 0u8.write(&mut w),
+            Some(s) => {
+                1u8.write(&mut w)?;
+                write_point(s, &mut w)
+            }
+        }
+    }
+}
+
+impl<G: AffineCurve> FromBytes for PolyComm<G> {
+    #[inline]
+    fn read<R: Read>(mut r: R) -> IoResult<Self> {
+        let unshifted = read_points(&mut r)?;
+        let shifted = match u8::read(&mut r)? {
+            0 => None,
+            1 => Some(read_point(&mut r)?),
+            _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid shifted tag")),
+        };
+        Ok(PolyComm { unshifted, shifted })
+    }
+}
+
 impl<A: Copy, B: Copy> PolyComm<(A, B)> {
     fn unzip(self) -> (PolyComm<A>, PolyComm<B>) {
         let a = self.map(|(x, _)| x);
@@ -101,6 +136,162 @@ impl<C: AffineCurve> PolyComm<C> {
     }
 }
 
+// Arithmetic on polynomial commitments. The segments are combined pointwise, a segment or
+// a shifted part missing from one of the operands standing for the commitment to zero
+impl<'a, 'b, C: AffineCurve> Add<&'a PolyComm<C>> for &'b PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn add(self, other: &'a PolyComm<C>) -> PolyComm<C> {
+        let add = |a: Option<&C>, b: Option<&C>| match (a, b) {
+            (Some(a), Some(b)) => {
+                let mut sum = a.into_projective();
+                sum.add_assign_mixed(b);
+                Some(sum.into_affine())
+            }
+            (Some(a), None) => Some(*a),
+            (None, b) => b.copied(),
+        };
+        let n = max(self.unshifted.len(), other.unshifted.len());
+        PolyComm {
+            unshifted: (0..n)
+                .map(|i| add(self.unshifted.get(i), other.unshifted.get(i)).unwrap())
+                .collect(),
+            shifted: add(self.shifted.as_ref(), other.shifted.as_ref()),
+        }
+    }
+}
+
+impl<'a, 'b, C: AffineCurve> Sub<&'a PolyComm<C>> for &'b PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn sub(self, other: &'a PolyComm<C>) -> PolyComm<C> {
+        self + &(-other)
+    }
+}
+
+impl<'a, C: AffineCurve> Neg for &'a PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn neg(self) -> PolyComm<C> {
+        self.map(|x| -x)
+    }
+}
+
+impl<'a, C: AffineCurve> Mul<C::ScalarField> for &'a PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn mul(self, elm: C::ScalarField) -> PolyComm<C> {
+        self.map(|x| x.mul(elm).into_affine())
+    }
+}
+
+impl<C: AffineCurve> Add for PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn add(self, other: PolyComm<C>) -> PolyComm<C> {
+        &self + &other
+    }
+}
+
+impl<C: AffineCurve> Sub for PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn sub(self, other: PolyComm<C>) -> PolyComm<C> {
+        &self - &other
+    }
+}
+
+impl<C: AffineCurve> Neg for PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn neg(self) -> PolyComm<C> {
+        -&self
+    }
+}
+
+impl<C: AffineCurve> Mul<C::ScalarField> for PolyComm<C> {
+    type Output = PolyComm<C>;
+
+    fn mul(self, elm: C::ScalarField) -> PolyComm<C> {
+        &self * elm
+    }
+}
+
+// Linear combination of polynomial commitments. The terms are only accumulated, the
+// combination being computed with a single multi-scalar multiplication per segment on eval
+#[derive(Clone, Debug)]
+pub struct PolyCommCombination<'a, C: AffineCurve> {
+    terms: Vec<(&'a PolyComm<C>, C::ScalarField)>,
+}
+
+impl<'a, C: AffineCurve> Default for PolyCommCombination<'a, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, C: AffineCurve> PolyCommCombination<'a, C> {
+    pub fn new() -> Self {
+        PolyCommCombination { terms: Vec::new() }
+    }
+
+    // This function adds the term scalar * comm to the combination
+    pub fn push(&mut self, comm: &'a PolyComm<C>, scalar: C::ScalarField) -> &mut Self {
+        self.terms.push((comm, scalar));
+        self
+    }
+
+    // This function adds the terms scalars[i] * comms[i] to the combination
+    //     comms: polynomial commitments
+    //     scalars: scalars, as many as the commitments
+    pub fn extend<I, S>(&mut self, comms: I, scalars: S) -> &mut Self
+    where
+        I: IntoIterator<Item = &'a PolyComm<C>>,
+        S: IntoIterator<Item = C::ScalarField>,
+    {
+        let comms: Vec<_> = comms.into_iter().collect();
+        let scalars: Vec<_> = scalars.into_iter().collect();
+        assert_eq!(comms.len(), scalars.len());
+        self.terms.extend(comms.into_iter().zip(scalars));
+        self
+    }
+
+    // This function multiplies every term of the combination by scalar
+    pub fn scale(&mut self, scalar: C::ScalarField) -> &mut Self {
+        self.terms.iter_mut().for_each(|(_, s)| *s *= &scalar);
+        self
+    }
+
+    // This function computes the combination
+    //     RETURN: the commitment to the combination of the committed polynomials
+    pub fn eval(&self) -> PolyComm<C> {
+        let msm = |segment: &dyn Fn(&PolyComm<C>) -> Option<C>| {
+            let (points, scalars): (Vec<_>, Vec<_>) = self
+                .terms
+                .iter()
+                .filter_map(|(comm, s)| segment(comm).map(|p| (p, s.into_repr())))
+                .unzip();
+            if points.is_empty() {
+                None
+            } else {
+                Some(VariableBaseMSM::multi_scalar_mul(&points, &scalars).into_affine())
+            }
+        };
+        let n = self
+            .terms
+            .iter()
+            .map(|(comm, _)| comm.unshifted.len())
+            .max()
+            .unwrap_or(0);
+        PolyComm {
+            unshifted: (0..n)
+                .map(|i| msm(&|comm| comm.unshifted.get(i).copied()).unwrap())
+                .collect(),
+            shifted: msm(&|comm| comm.shifted),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "ocaml_types", derive(ocaml::IntoValue, ocaml::FromValue))]
 pub struct OpeningProof<G: AffineCurve> {
@@ -111,11 +302,68 @@ pub struct OpeningProof<G: AffineCurve> {
     pub sg: G,
 }
 
+impl<G: AffineCurve> ToBytes for OpeningProof<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut w: W) -> IoResult<()> {
+        (self.lr.len() as u64).write(&mut w)?;
+        for (l, r) in self.lr.iter() {
+            write_point(l, &mut w)?;
+            write_point(r, &mut w)?;
+        }
+        write_point(&self.delta, &mut w)?;
+        self.z1.write(&mut w)?;
+        self.z2.write(&mut w)?;
+        write_point(&self.sg, &mut w)
+    }
+}
+
+impl<G: AffineCurve> FromBytes for OpeningProof<G> {
+    #[inline]
+    fn read<R: Read>(mut r: R) -> IoResult<Self> {
+        let rounds = u64::read(&mut r)?;
+        let mut lr = vec![];
+        for _ in 0..rounds {
+            let left = read_point(&mut r)?;
+            let right = read_point(&mut r)?;
+            lr.push((left, right));
+        }
+        let delta = read_point(&mut r)?;
+        let z1 = G::ScalarField::read(&mut r)?;
+        let z2 = G::ScalarField::read(&mut r)?;
+        let sg = read_point(&mut r)?;
+        Ok(OpeningProof {
+            lr,
+            delta,
+            z1,
+            z2,
+            sg,
+        })
+    }
+}
+
+// Opening of polynomials at their own sets of evaluation points: the evaluations
+// of the polynomials at the points outside their sets complete the batch to
+// an opening of all the polynomials at all the points, proven by a single proof
+#[derive(Clone, Debug)]
+pub struct MultiPointOpeningProof<G: AffineCurve> {
+    // for every polynomial, segment evaluations at the points outside its set
+    pub evals: Vec<Vec<Vec<G::ScalarField>>>,
+    pub proof: OpeningProof<G>,
+}
+
 pub struct Challenges<F> {
     pub chal: Vec<F>,
     pub chal_inv: Vec<F>,
 }
 
+// The deferred part of the verification of an opening proof: sg has to be the commitment
+// to the polynomial b whose coefficients are determined by the challenges
+#[derive(Clone, Debug)]
+pub struct Accumulator<G: AffineCurve> {
+    pub chals: Vec<G::ScalarField>,
+    pub sg: G,
+}
+
 impl<G: AffineCurve> OpeningProof<G>
 where
     G::ScalarField: CommitmentField,
@@ -195,6 +443,106 @@ pub fn b_poly_coefficients<F: Field>(chals: &[F]) -> Vec<F> {
     s
 }
 
+// The polynomial b of the challenges of an opening proof
+//     b(X) = prod_i (1 + chals[i] X^(2^(k - 1 - i)))
+// its coefficients, which only the prover needs, being computed once and cached
+#[derive(Clone, Debug)]
+pub struct ChallengePolynomial<F> {
+    pub chals: Vec<F>,
+    coefficients: Option<Vec<F>>,
+}
+
+impl<F: Field> ChallengePolynomial<F> {
+    pub fn new(chals: Vec<F>) -> Self {
+        ChallengePolynomial {
+            chals,
+            coefficients: None,
+        }
+    }
+
+    // This function returns the number of coefficients of the polynomial
+    pub fn size(&self) -> usize {
+        1 << self.chals.len()
+    }
+
+    // This function evaluates the polynomial in O(log n)
+    pub fn evaluate(&self, x: F) -> F {
+        b_poly(&self.chals, x)
+    }
+
+    // This function computes the coefficient of the given power in O(log n), the coefficient
+    // being the product of the challenges matching the bits of the power
+    pub fn coefficient(&self, j: usize) -> F {
+        let k = self.chals.len();
+        if j >= self.size() {
+            return F::zero();
+        }
+        product(
+            (0..k)
+                .filter(|i| j & (1 << (k - 1 - i)) != 0)
+                .map(|i| self.chals[i]),
+        )
+    }
+
+    // This function evaluates the tail of the polynomial starting at the given power,
+    // without computing its coefficients
+    //     start: first power of the tail
+    //     x: evaluation point
+    //     RETURN: sum_{j >= start} b_j x^(j - start)
+    pub fn evaluate_tail(&self, start: usize, x: F) -> F {
+        let k = self.chals.len();
+        if start >= self.size() {
+            return F::zero();
+        }
+
+        // suffix[i] = prod_{i' >= i} (1 + chals[i'] x^(2^(k - 1 - i'))), the polynomial of the
+        // challenges past i
+        let mut suffix = vec![F::one(); k + 1];
+        let mut pow_two = x;
+        for i in (0..k).rev() {
+            suffix[i] = suffix[i + 1] * &(F::one() + &(self.chals[i] * &pow_two));
+            pow_two.square_in_place();
+        }
+
+        // the powers [m, 2^(k - i)) of the polynomial of the challenges past i split at the
+        // half: the upper half shares the factor chals[i], the lower one recurses
+        let mut res = F::zero();
+        let mut scale = F::one();
+        let mut m = start;
+        for i in 0..k {
+            let half = 1 << (k - 1 - i);
+            if m >= half {
+                scale *= &self.chals[i];
+                m -= half;
+            } else {
+                let upper = self.chals[i] * &x.pow(&[(half - m) as u64]) * &suffix[i + 1];
+                res += &(scale * &upper);
+            }
+        }
+        res + &scale
+    }
+
+    // This function returns the coefficients of the polynomial, computing them on first use
+    pub fn coefficients(&mut self) -> &[F] {
+        let chals = &self.chals;
+        self.coefficients
+            .get_or_insert_with(|| b_poly_coefficients(chals))
+    }
+
+    // This function commits to the polynomial, with no blinding, in segments of the SRS size
+    pub fn commit<G: CommitmentCurve<ScalarField = F>>(&mut self, srs: &SRS<G>) -> PolyComm<G> {
+        let n = srs.g.len();
+        PolyComm {
+            unshifted: self
+                .coefficients()
+                .chunks(n)
+                .map(|c| srs.g_msm(0, c).into_affine())
+                .collect(),
+            shifted: None,
+        }
+    }
+}
+
 pub fn ceil_log2(d: usize) -> usize {
     let mut pow2 = 1;
     let mut ceil_log2 = 0;
@@ -222,6 +570,41 @@ fn squeeze_prechallenge<Fq: Field, G, Fr: SquareRootField, EFqSponge: FqSponge<F
     ScalarChallenge(sponge.challenge())
 }
 
+// This function computes the indices of the evaluation points outside of a point set
+//     points: indices of the points of the set
+//     n: number of evaluation points
+fn complement(points: &[usize], n: usize) -> Vec<usize> {
+    (0..n).filter(|j| !points.contains(j)).collect()
+}
+
+// This function absorbs the evaluations completing a multi-point opening and rescales
+// the scaling factors of the opening with fresh challenges, so that they are bound to
+// all the evaluations, including the ones chosen by the prover
+//     RETURN: scaling factors for polynoms and for evaluation point powers
+fn multi_point_scales<
+    Fq: Field,
+    G,
+    Fr: PrimeField + CommitmentField,
+    EFqSponge: FqSponge<Fq, G, Fr>,
+>(
+    endo_r: &Fr,
+    sponge: &mut EFqSponge,
+    polyscale: Fr,
+    evalscale: Fr,
+    evals: &[Vec<Vec<Fr>>],
+) -> (Fr, Fr) {
+    let evals: Vec<Fr> = evals
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|e| shift_scalar(*e))
+        .collect();
+    sponge.absorb_fr(&evals);
+    let xi = squeeze_challenge(endo_r, sponge);
+    let r = squeeze_challenge(endo_r, sponge);
+    (polyscale * &xi, evalscale * &r)
+}
+
 fn squeeze_challenge<
     Fq: Field,
     G,
@@ -241,6 +624,24 @@ pub trait CommitmentCurve: AffineCurve {
     fn to_coordinates(&self) -> Option<(Self::BaseField, Self::BaseField)>;
     fn of_coordinates(x: Self::BaseField, y: Self::BaseField) -> Self;
 
+    // Whether the point is on the curve and in its prime order subgroup, the identity included
+    fn is_valid(&self) -> bool;
+
+    // Precomputation of the multi-scalar multiplication using the endomorphism, computed once
+    // per SRS
+    type Glv: Clone + std::fmt::Debug + Send + Sync;
+
+    // This function precomputes the multi-scalar multiplication for the endomorphism
+    //     endo_q: base field coefficient of the endomorphism
+    //     endo_r: scalar field eigenvalue of the endomorphism
+    fn glv(endo_q: Self::BaseField, endo_r: Self::ScalarField) -> Self::Glv;
+
+    // Multi-scalar multiplication, glv being the precomputation of the endomorphism
+    fn msm(_glv: &Self::Glv, bases: &[Self], scalars: &[Self::ScalarField]) -> Self::Projective {
+        let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+        VariableBaseMSM::multi_scalar_mul(bases, &scalars)
+    }
+
     // Combine where x1 = one
     fn combine_one(g1: &Vec<Self>, g2: &Vec<Self>, x2: Self::ScalarField) -> Vec<Self> {
         crate::combine::window_combine(g1, g2, Self::ScalarField::one(), x2)
@@ -262,6 +663,9 @@ where
 {
     type Params = P;
     type Map = BWParameters<P>;
+    // the decomposition, None for the curves without the endomorphism, whose multi-scalar
+    // multiplication falls back to the one of zexe
+    type Glv = Option<Glv<P>>;
 
     fn to_coordinates(&self) -> Option<(Self::BaseField, Self::BaseField)> {
         if self.infinity {
@@ -275,6 +679,33 @@ where
         SWJAffine::<P>::new(x, y, false)
     }
 
+    fn is_valid(&self) -> bool {
+        // the subgroup check is a scalar multiplication, which the curves of prime order skip
+        let prime_order = P::COFACTOR
+            .iter()
+            .enumerate()
+            .all(|(i, c)| *c == if i == 0 { 1 } else { 0 });
+        self.is_on_curve() && (prime_order || self.is_in_correct_subgroup_assuming_on_curve())
+    }
+
+    fn glv(endo_q: Self::BaseField, endo_r: Self::ScalarField) -> Option<Glv<P>> {
+        Glv::new(endo_q, endo_r)
+    }
+
+    fn msm(
+        glv: &Option<Glv<P>>,
+        bases: &[Self],
+        scalars: &[Self::ScalarField],
+    ) -> Self::Projective {
+        match glv {
+            Some(glv) => glv.msm(bases, scalars),
+            None => {
+                let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+                VariableBaseMSM::multi_scalar_mul(bases, &scalars)
+            }
+        }
+    }
+
     fn combine_one(g1: &Vec<Self>, g2: &Vec<Self>, x2: Self::ScalarField) -> Vec<Self> {
         crate::combine::affine_window_combine_one(g1, g2, x2)
     }
@@ -289,12 +720,97 @@ where
     }
 }
 
-fn to_group<G: CommitmentCurve>(m: &G::Map, t: <G as AffineCurve>::BaseField) -> G {
+pub(crate) fn to_group<G: CommitmentCurve>(m: &G::Map, t: <G as AffineCurve>::BaseField) -> G {
     let (x, y) = m.to_group(t);
     G::of_coordinates(x, y)
 }
 
-pub fn combined_inner_product<G: CommitmentCurve>(
+// This function combines a batch of polynomials, the segments of every polynomial and,
+// if bounded, its last segment shifted to the end of the segment size, scaled by the
+// powers of the polynomial scaling factor
+//     n: segment size
+//     plnms: batch of polynomials with, optionally, max degrees and commitment randomness
+//     polyscale: polynomial scaling factor
+//     RETURN: combined polynomial and combined commitment randomness
+pub(crate) fn combined_polynomial<F: Field>(
+    n: usize,
+    plnms: &[(&DensePolynomial<F>, Option<usize>, PolyComm<F>)],
+    polyscale: F,
+) -> (DensePolynomial<F>, F) {
+    let mut p = DensePolynomial::<F>::zero();
+
+    let mut omega = F::zero();
+    let mut scale = F::one();
+
+    // the chunk of the polynomial starting at the given offset, empty past its end
+    let segment = |p_i: &DensePolynomial<F>, offset: usize| {
+        let len = p_i.coeffs.len();
+        DensePolynomial::<F>::from_coefficients_slice(
+            &p_i.coeffs[min(offset, len)..min(offset + n, len)],
+        )
+    };
+
+    // iterating over polynomials in the batch
+    // the blinders have the shape of the commitment, so they determine the segments
+    // to be opened; in particular a hiding commitment to the zero polynomial is
+    // opened as a single zero segment
+    for (p_i, degree_bound, omegas) in plnms.iter() {
+        assert!(p_i.coeffs.len() <= omegas.unshifted.len() * n);
+        // always mixing in the unshifted segments
+        for (j, omega_j) in omegas.unshifted.iter().enumerate() {
+            p += &segment(p_i, j * n).scale(scale);
+            omega += &(*omega_j * scale);
+            scale *= &polyscale;
+        }
+        match (degree_bound, omegas.shifted) {
+            (Some(m), Some(omega_s)) => {
+                assert!(p_i.coeffs.len() <= m + 1);
+                // mixing in the segment containing the degree bound, shifted to the end
+                // of SRS since degree is bounded
+                let start = m - m % n;
+                let shift = n - m % n;
+                p += &(segment(p_i, start).shiftr(shift).scale(scale));
+                omega += &(omega_s * scale);
+                scale *= &polyscale;
+            }
+            (Some(m), None) => assert!(p_i.coeffs.len() <= m + 1),
+            (None, shifted) => assert!(shifted.is_none()),
+        }
+    }
+    (p, omega)
+}
+
+// This function lists the terms of the combination of a batch of commitments,
+// matching the combination of the polynomials by combined_polynomial
+//     polys: batch of polycommitments, evaluation vectors and, optionally, max degrees
+//     xi: polynomial scaling factor
+//     RETURN: commitments with their scaling factors
+pub(crate) fn combined_commitment_terms<G: AffineCurve>(
+    polys: &[(&PolyComm<G>, Vec<&Vec<Fr<G>>>, Option<usize>)],
+    xi: Fr<G>,
+) -> Vec<(G, Fr<G>)> {
+    let mut terms = vec![];
+    let mut xi_i = Fr::<G>::one();
+
+    for (comm, _evals_tr, shifted) in polys.iter().filter(|x| x.0.unshifted.len() > 0) {
+        // iterating over the polynomial segments
+        for comm_ch in comm.unshifted.iter() {
+            terms.push((*comm_ch, xi_i));
+            xi_i *= xi;
+        }
+
+        if let Some(_m) = shifted {
+            if let Some(comm_ch) = comm.shifted {
+                // xi^i sum_j r^j elm_j^{N - m} f(elm_j)
+                terms.push((comm_ch, xi_i));
+                xi_i *= xi;
+            }
+        }
+    }
+    terms
+}
+
+pub fn combined_inner_product<G: AffineCurve>(
     evaluation_points: &[Fr<G>],
     xi: &Fr<G>,
     r: &Fr<G>,
@@ -320,7 +836,7 @@ pub fn combined_inner_product<G: CommitmentCurve>(
 
         if let Some(m) = shifted {
             // xi^i sum_j r^j elm_j^{N - m} f(elm_j)
-            let last_evals = if *m > evals.len() * srs_length {
+            let last_evals = if *m >= evals.len() * srs_length {
                 vec![Fr::<G>::zero(); evaluation_points.len()]
             } else {
                 evals[evals.len() - 1].clone()
@@ -348,21 +864,43 @@ where
         max: Option<usize>,
         rng: &mut dyn RngCore,
     ) -> (PolyComm<G>, PolyComm<Fr<G>>) {
-        self.mask(self.commit_non_hiding(plnm, max), rng)
+        self.commit_maybe_hiding(plnm, max, true, rng)
+    }
+
+    // This function commits a polynomial with the shape of the hiding commitment, blinding it
+    // only if hiding, for the provers which need no zero-knowledge
+    //     plnm: polynomial to commit to
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     hiding: whether to blind the commitment
+    //     RETURN: tuple of: commitment, commitment randomness, public if not hiding
+    pub fn commit_maybe_hiding(
+        &self,
+        plnm: &DensePolynomial<Fr<G>>,
+        max: Option<usize>,
+        hiding: bool,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<G>, PolyComm<Fr<G>>) {
+        let mut c = self.commit_non_hiding(plnm, max);
+        // the zero polynomial is committed as a single blinded segment,
+        // so that the number of segments does not reveal it
+        if c.unshifted.is_empty() {
+            c.unshifted.push(G::zero());
+        }
+        if hiding {
+            self.mask(c, rng)
+        } else {
+            c.map(|g: G| (g, Fr::<G>::zero())).unzip()
+        }
     }
 
     fn mask(&self, c: PolyComm<G>, rng: &mut dyn RngCore) -> (PolyComm<G>, PolyComm<Fr<G>>) {
+        // the identity is blinded as well, so that commitments to zero segments
+        // are indistinguishable from any other commitment
         c.map(|g: G| {
-            if g.is_zero() {
-                // TODO: This leaks information when g is the identity!
-                // We should change this so that we still mask in this case
-                (g, Fr::<G>::zero())
-            } else {
-                let w = Fr::<G>::rand(rng);
-                let mut g_masked = self.h.mul(w);
-                g_masked.add_assign_mixed(&g);
-                (g_masked.into_affine(), w)
-            }
+            let w = Fr::<G>::rand(rng);
+            let mut g_masked = self.h_mul(w);
+            g_masked.add_assign_mixed(&g);
+            (g_masked.into_affine(), w)
         })
         .unzip()
     }
@@ -376,52 +914,96 @@ where
         plnm: &DensePolynomial<Fr<G>>,
         max: Option<usize>,
     ) -> PolyComm<G> {
-        let n = self.g.len();
-        let p = plnm.coeffs.len();
+        commit_segments(self.g.len(), plnm, max, &|offset, coeffs| {
+            self.g_msm(offset, coeffs)
+        })
+    }
 
-        // committing all the segments without shifting
-        let unshifted = if plnm.is_zero() {
-            Vec::new()
-        } else {
-            (0..p / n + if p % n != 0 { 1 } else { 0 })
-                .map(|i| {
-                    VariableBaseMSM::multi_scalar_mul(
-                        &self.g,
-                        &plnm.coeffs[i * n..p]
-                            .iter()
-                            .map(|s| s.into_repr())
-                            .collect::<Vec<_>>(),
-                    )
-                    .into_affine()
-                })
-                .collect()
-        };
+    // This function commits a polynomial whose coefficients are streamed, committing its
+    // segments in batches of a segment per thread as they are read
+    //     coeffs: coefficients of the polynomial, lowest degree first
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     RETURN: tuple of: hiding commitment, as by commit, blinders
+    pub fn commit_iter<I: Iterator<Item = Fr<G>>>(
+        &self,
+        coeffs: I,
+        max: Option<usize>,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<G>, PolyComm<Fr<G>>) {
+        let mut c = self.commit_non_hiding_iter(coeffs, max);
+        if c.unshifted.is_empty() {
+            c.unshifted.push(G::zero());
+        }
+        self.mask(c, rng)
+    }
 
-        // committing only last segment shifted to the right edge of SRS
-        let shifted = match max {
-            None => None,
-            Some(max) => {
-                let start = max - (max % n);
-                if plnm.is_zero() || start >= p {
-                    Some(G::zero())
-                } else if max % n == 0 {
-                    None
-                } else {
-                    Some(
-                        VariableBaseMSM::multi_scalar_mul(
-                            &self.g[n - (max % n)..],
-                            &plnm.coeffs[start..p]
-                                .iter()
-                                .map(|s| s.into_repr())
-                                .collect::<Vec<_>>(),
-                        )
-                        .into_affine(),
-                    )
-                }
-            }
-        };
+    // This function commits a polynomial whose coefficients are streamed, committing its
+    // segments in batches of a segment per thread as they are read
+    //     coeffs: coefficients of the polynomial, lowest degree first
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     RETURN: unblinded commitment, as by commit_non_hiding
+    pub fn commit_non_hiding_iter<I: Iterator<Item = Fr<G>>>(
+        &self,
+        coeffs: I,
+        max: Option<usize>,
+    ) -> PolyComm<G> {
+        commit_segments_stream(self.g.len(), coeffs.map(Ok), max, &|offset, coeffs| {
+            self.g_msm(offset, coeffs)
+        })
+        .unwrap()
+    }
+
+    // This function commits a polynomial whose coefficients are read, as written by
+    // serialization::write_fields, without reading them all in memory
+    //     reader: source of the length and the coefficients of the polynomial
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     RETURN: unblinded commitment, as by commit_non_hiding
+    pub fn commit_non_hiding_reader<R: Read>(
+        &self,
+        mut reader: R,
+        max: Option<usize>,
+    ) -> IoResult<PolyComm<G>> {
+        let len = read_len(&mut reader)?;
+        commit_segments_stream(
+            self.g.len(),
+            (0..len).map(|_| Fr::<G>::read(&mut reader)),
+            max,
+            &|offset, coeffs| self.g_msm(offset, coeffs),
+        )
+    }
+
+    // This function commits a polynomial given by its evaluations over the domain
+    // against the cached Lagrange basis of the domain, without interpolating it
+    //     domain: evaluation domain, its Lagrange basis has to be added to SRS
+    //     evals: evaluations of the polynomial over the domain
+    //     RETURN: tuple of: hiding commitment with a segment per SRS length of the domain, blinders
+    pub fn commit_evaluations(
+        &self,
+        domain: D<Fr<G>>,
+        evals: &Evaluations<Fr<G>, D<Fr<G>>>,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<G>, PolyComm<Fr<G>>) {
+        self.mask(self.commit_evaluations_non_hiding(domain, evals), rng)
+    }
 
-        PolyComm::<G> { unshifted, shifted }
+    // This function commits a polynomial given by its evaluations over the domain
+    // against the cached Lagrange basis of the domain, without interpolating it
+    //     domain: evaluation domain, its Lagrange basis has to be added to SRS
+    //     evals: evaluations of the polynomial over the domain
+    //     RETURN: unblinded commitment with a segment per SRS length of the domain
+    pub fn commit_evaluations_non_hiding(
+        &self,
+        domain: D<Fr<G>>,
+        evals: &Evaluations<Fr<G>, D<Fr<G>>>,
+    ) -> PolyComm<G> {
+        assert_eq!(evals.evals.len(), domain.size());
+        let basis = self.lagrange_basis(domain).unwrap_or_else(|| {
+            panic!(
+                "Lagrange basis of the domain of size {} was not added to SRS",
+                domain.size()
+            )
+        });
+        PolyComm::<G>::multi_scalar_mul(&basis.iter().collect(), &evals.evals)
     }
 
     // This function opens polynomial commitments in batch
@@ -432,6 +1014,27 @@ where
     //     oracle_params: parameters for the random oracle argument
     //     RETURN: commitment opening proof
     pub fn open<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        plnms: Vec<(&DensePolynomial<Fr<G>>, Option<usize>, PolyComm<Fr<G>>)>, // vector of polynomial with optional degree bound and commitment randomness
+        elm: &Vec<Fr<G>>,  // vector of evaluation points
+        polyscale: Fr<G>,  // scaling factor for polynoms
+        evalscale: Fr<G>,  // scaling factor for evaluation point powers
+        sponge: EFqSponge, // sponge
+        rng: &mut dyn RngCore,
+    ) -> OpeningProof<G> {
+        self.open_maybe_hiding(
+            group_map, plnms, elm, polyscale, evalscale, sponge, true, rng,
+        )
+    }
+
+    // This function opens polynomial commitments in batch as open does, blinding the L and R
+    // commitments of the rounds only if hiding, for the provers which need no zero-knowledge.
+    // The nonces of delta are drawn from the randomness source either way, so that delta is
+    // distinct from the identity, as the validation of opening proofs expects
+    //     hiding: whether to blind the L and R commitments of the rounds
+    //     RETURN: commitment opening proof
+    pub fn open_maybe_hiding<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>>(
         &self,
         group_map: &G::Map,
         plnms: Vec<(&DensePolynomial<Fr<G>>, Option<usize>, PolyComm<Fr<G>>)>, // vector of polynomial with optional degree bound and commitment randomness
@@ -439,76 +1042,18 @@ where
         polyscale: Fr<G>,      // scaling factor for polynoms
         evalscale: Fr<G>,      // scaling factor for evaluation point powers
         mut sponge: EFqSponge, // sponge
+        hiding: bool,
         rng: &mut dyn RngCore,
     ) -> OpeningProof<G> {
-        let rounds = ceil_log2(self.g.len());
-        let padded_length = 1 << rounds;
-
-        // TODO: Trim this to the degree of the largest polynomial
-
-        let padding = padded_length - self.g.len();
-        let mut g = self.g.clone();
-        g.extend(vec![G::zero(); padding]);
-
         // scale the polynoms in accumulator shifted, if bounded, to the end of SRS
-        let (p, blinding_factor) = {
-            let mut p = DensePolynomial::<Fr<G>>::zero();
-
-            let mut omega = Fr::<G>::zero();
-            let mut scale = Fr::<G>::one();
-
-            // iterating over polynomials in the batch
-            for (p_i, degree_bound, omegas) in plnms.iter().filter(|p| p.0.is_zero() == false) {
-                let mut offset = 0;
-                let mut j = 0;
-                // iterating over chunks of the polynomial
-                if let Some(m) = degree_bound {
-                    assert!(p_i.coeffs.len() <= m + 1);
-                    while offset < p_i.coeffs.len() {
-                        let segment = DensePolynomial::<Fr<G>>::from_coefficients_slice(
-                            &p_i.coeffs[offset..if offset + self.g.len() > p_i.coeffs.len() {
-                                p_i.coeffs.len()
-                            } else {
-                                offset + self.g.len()
-                            }],
-                        );
-                        // always mixing in the unshifted segments
-                        p += &segment.scale(scale);
-                        omega += &(omegas.unshifted[j] * scale);
-                        j += 1;
-                        scale *= &polyscale;
-                        offset += self.g.len();
-                        if offset > *m {
-                            // mixing in the shifted segment since degree is bounded
-                            p += &(segment.shiftr(self.g.len() - m % self.g.len()).scale(scale));
-                            omega += &(omegas.shifted.unwrap() * scale);
-                            scale *= &polyscale;
-                        }
-                    }
-                } else {
-                    assert!(omegas.shifted.is_none());
-                    while offset < p_i.coeffs.len() {
-                        let segment = DensePolynomial::<Fr<G>>::from_coefficients_slice(
-                            &p_i.coeffs[offset..if offset + self.g.len() > p_i.coeffs.len() {
-                                p_i.coeffs.len()
-                            } else {
-                                offset + self.g.len()
-                            }],
-                        );
-                        // always mixing in the unshifted segments
-                        p += &segment.scale(scale);
-                        omega += &(omegas.unshifted[j] * scale);
-                        j += 1;
-                        scale *= &polyscale;
-                        offset += self.g.len();
-                    }
-                }
-                assert_eq!(j, omegas.unshifted.len());
-            }
-            (p, omega)
-        };
+        let (p, blinding_factor) = combined_polynomial(self.g.len(), &plnms, polyscale);
 
-        let rounds = ceil_log2(self.g.len());
+        // the opening only runs over the prefix of SRS covering the combined polynomial,
+        // which spans the whole SRS as soon as a degree bounded segment is shifted to its end
+        let rounds = ceil_log2(p.coeffs.len());
+        let padded_length = 1 << rounds;
+        let mut g = self.g[0..min(padded_length, self.g.len())].to_vec();
+        g.extend(vec![G::zero(); padded_length - g.len()]);
 
         // b_j = sum_i r^i elm_i^j
         let b_init = {
@@ -531,6 +1076,7 @@ where
             .map(|(a, b)| *a * b)
             .fold(Fr::<G>::zero(), |acc, x| acc + x);
 
+        sponge.label("opening");
         sponge.absorb_fr(&[shift_scalar(combined_inner_product)]);
 
         let t = sponge.challenge_fq();
@@ -549,34 +1095,39 @@ where
         let mut chals = vec![];
         let mut chal_invs = vec![];
 
-        for _ in 0..rounds {
+        for round in 0..rounds {
             let n = g.len() / 2;
             let (g_lo, g_hi) = (g[0..n].to_vec(), g[n..].to_vec());
             let (a_lo, a_hi) = (&a[0..n], &a[n..]);
             let (b_lo, b_hi) = (&b[0..n], &b[n..]);
 
-            let rand_l = Fr::<G>::rand(rng);
-            let rand_r = Fr::<G>::rand(rng);
+            let (rand_l, rand_r) = if hiding {
+                (Fr::<G>::rand(rng), Fr::<G>::rand(rng))
+            } else {
+                (Fr::<G>::zero(), Fr::<G>::zero())
+            };
 
-            let l = VariableBaseMSM::multi_scalar_mul(
-                &[&g[0..n], &[self.h, u]].concat(),
-                &[&a[n..], &[rand_l, inner_prod(a_hi, b_lo)]]
-                    .concat()
-                    .iter()
-                    .map(|x| x.into_repr())
-                    .collect::<Vec<_>>(),
-            )
-            .into_affine();
+            let (l, r) = if round == 0 && self.fixed_base.is_some() {
+                // g is still the prefix of SRS, so its fixed-base tables apply
+                let l = self.g_msm(0, a_hi) + &self.h_mul(rand_l) + &u.mul(inner_prod(a_hi, b_lo));
+                let r = self.g_msm(n, a_lo) + &self.h_mul(rand_r) + &u.mul(inner_prod(a_lo, b_hi));
+                (l.into_affine(), r.into_affine())
+            } else {
+                let l = self
+                    .msm(
+                        &[&g[0..n], &[self.h, u]].concat(),
+                        &[&a[n..], &[rand_l, inner_prod(a_hi, b_lo)]].concat(),
+                    )
+                    .into_affine();
 
-            let r = VariableBaseMSM::multi_scalar_mul(
-                &[&g[n..], &[self.h, u]].concat(),
-                &[&a[0..n], &[rand_r, inner_prod(a_lo, b_hi)]]
-                    .concat()
-                    .iter()
-                    .map(|x| x.into_repr())
-                    .collect::<Vec<_>>(),
-            )
-            .into_affine();
+                let r = self
+                    .msm(
+                        &[&g[n..], &[self.h, u]].concat(),
+                        &[&a[0..n], &[rand_r, inner_prod(a_lo, b_hi)]].concat(),
+                    )
+                    .into_affine();
+                (l, r)
+            };
 
             lr.push((l, r));
             blinders.push((rand_l, rand_r));
@@ -632,7 +1183,7 @@ where
         let r_delta = Fr::<G>::rand(rng);
 
         let delta = ((g0.into_projective() + &(u.mul(b0))).into_affine().mul(d)
-            + &self.h.mul(r_delta))
+            + &self.h_mul(r_delta))
             .into_affine();
 
         sponge.absorb_g(&[delta]);
@@ -650,6 +1201,69 @@ where
         }
     }
 
+    // This function opens polynomial commitments in batch, every polynomial at its own set
+    // of evaluation points, with a single opening proof
+    //     plnms: batch of polynomials to open commitments for with, optionally, max degrees
+    //         and the indices in elm of the points to open them at
+    //     elm: evaluation point vector, union of the point sets of the polynomials
+    //     polyscale: polynomial scaling factor for opening commitments in batch
+    //     evalscale: eval scaling factor for opening commitments in batch
+    //     RETURN: commitment opening proof with the evaluations outside the point sets
+    pub fn open_multi_point<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        plnms: Vec<(
+            &DensePolynomial<Fr<G>>,
+            Option<usize>,
+            PolyComm<Fr<G>>,
+            Vec<usize>,
+        )>, // vector of polynomial with optional degree bound, commitment randomness and point set
+        elm: &Vec<Fr<G>>,      // vector of evaluation points
+        polyscale: Fr<G>,      // scaling factor for polynoms
+        evalscale: Fr<G>,      // scaling factor for evaluation point powers
+        mut sponge: EFqSponge, // sponge
+        rng: &mut dyn RngCore,
+    ) -> MultiPointOpeningProof<G> {
+        let n = self.g.len();
+        // the segments of the evaluations follow the blinders, as the segments opened by open
+        let evals: Vec<Vec<Vec<Fr<G>>>> = plnms
+            .iter()
+            .map(|(p, _, omegas, points)| {
+                assert!(points.iter().all(|j| *j < elm.len()));
+                let len = p.coeffs.len();
+                complement(points, elm.len())
+                    .iter()
+                    .map(|j| {
+                        (0..omegas.unshifted.len())
+                            .map(|k| {
+                                DensePolynomial::<Fr<G>>::eval_polynomial(
+                                    &p.coeffs[min(k * n, len)..min((k + 1) * n, len)],
+                                    elm[*j],
+                                )
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (polyscale, evalscale) =
+            multi_point_scales(&self.endo_r, &mut sponge, polyscale, evalscale, &evals);
+        let proof = self.open(
+            group_map,
+            plnms
+                .into_iter()
+                .map(|(p, bound, omegas, _)| (p, bound, omegas))
+                .collect(),
+            elm,
+            polyscale,
+            evalscale,
+            sponge,
+            rng,
+        );
+        MultiPointOpeningProof { evals, proof }
+    }
+
     // This function verifies batch of batched polynomial commitment opening proofs
     //     batch: batch of batched polynomial commitment opening proofs
     //          vector of evaluation points
@@ -677,6 +1291,118 @@ where
         )>,
         rng: &mut dyn RngCore,
     ) -> bool {
+        let srs_lengths = vec![self.g.len(); batch.len()];
+        self.verify_prefixes::<EFqSponge>(group_map, batch, &srs_lengths, rng)
+    }
+
+    // This function verifies batch of multi-point opening proofs
+    //     batch: batch of multi-point opening proofs
+    //          vector of evaluation points, union of the point sets of the polynomials
+    //          polynomial scaling factor for this batched openinig proof
+    //          eval scaling factor for this batched openinig proof
+    //          batch/vector of polycommitments (opened in this batch), evaluation vectors
+    //              with the indices of their evaluation points and, optionally, max degrees
+    //          multi-point opening proof for this batched opening
+    //     randomness source context
+    //     RETURN: verification status
+    pub fn verify_multi_point<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut Vec<(
+            EFqSponge,
+            Vec<Fr<G>>, // vector of evaluation points
+            Fr<G>,      // scaling factor for polynoms
+            Fr<G>,      // scaling factor for evaluation point powers
+            Vec<(
+                &PolyComm<G>,              // polycommitment
+                Vec<(usize, &Vec<Fr<G>>)>, // vector of evaluation point indices and evaluations
+                Option<usize>,             // optional degree bound
+            )>,
+            &MultiPointOpeningProof<G>, // multi-point opening proof
+        )>,
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        let mut completed = vec![];
+        for (sponge, elm, polyscale, evalscale, polys, proof) in batch.iter() {
+            if proof.evals.len() != polys.len() {
+                return false;
+            }
+
+            // the evaluations of every polynomial at all the points, in the order of the points
+            let mut evals = vec![];
+            for ((comm, given, bound), extra) in polys.iter().zip(proof.evals.iter()) {
+                let points: Vec<usize> = given.iter().map(|(j, _)| *j).collect();
+                let rest = complement(&points, elm.len());
+                if extra.len() != rest.len() {
+                    return false;
+                }
+                let mut e: Vec<Option<&Vec<Fr<G>>>> = vec![None; elm.len()];
+                for (j, v) in given.iter() {
+                    if *j >= elm.len() || e[*j].is_some() {
+                        return false;
+                    }
+                    e[*j] = Some(*v);
+                }
+                for (j, v) in rest.iter().zip(extra.iter()) {
+                    e[*j] = Some(v);
+                }
+                let e: Vec<&Vec<Fr<G>>> = e.into_iter().map(|v| v.unwrap()).collect();
+                if e.iter().any(|v| v.len() != e[0].len()) {
+                    return false;
+                }
+                evals.push((*comm, e, *bound));
+            }
+
+            let mut sponge = sponge.clone();
+            let (polyscale, evalscale) = multi_point_scales(
+                &self.endo_r,
+                &mut sponge,
+                *polyscale,
+                *evalscale,
+                &proof.evals,
+            );
+            completed.push((
+                sponge,
+                elm.clone(),
+                polyscale,
+                evalscale,
+                evals,
+                &proof.proof,
+            ));
+        }
+        self.verify::<EFqSponge>(group_map, &mut completed, rng)
+    }
+
+    // This function verifies batch of batched polynomial commitment opening proofs
+    // made against SRS instances of possibly smaller depth, which are prefixes of this SRS
+    //     batch: batch of batched polynomial commitment opening proofs, as in verify
+    //     srs_lengths: lengths of the SRS instances the proofs of the batch were made against
+    //     randomness source context
+    //     RETURN: verification status
+    pub fn verify_prefixes<EFqSponge: FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut Vec<(
+            EFqSponge,
+            Vec<Fr<G>>, // vector of evaluation points
+            Fr<G>,      // scaling factor for polynoms
+            Fr<G>,      // scaling factor for evaluation point powers
+            Vec<(
+                &PolyComm<G>,     // polycommitment
+                Vec<&Vec<Fr<G>>>, // vector of evaluations
+                Option<usize>,    // optional degree bound
+            )>,
+            &OpeningProof<G>, // batched opening proof
+        )>,
+        srs_lengths: &[usize],
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        if srs_lengths.len() != batch.len()
+            || srs_lengths.iter().any(|n| *n == 0 || *n > self.g.len())
+        {
+            return false;
+        }
+
         // Verifier checks for all i,
         // c_i Q_i + delta_i = z1_i (G_i + b_i U_i) + z2_i H
         //
@@ -697,20 +1423,13 @@ where
         // We also check that the sg component of the proof is equal to the polynomial commitment
         // to the "s" array
 
-        let nonzero_length = self.g.len();
-
-        let max_rounds = ceil_log2(nonzero_length);
+        // the generators g are multiplied apart from the other points, borrowed from SRS. The
+        // scalars of the generators past the SRS of every proof are zero, the coefficients of s
+        // past it being dropped, so the generators are not padded to a power of two
+        let mut g_scalars = vec![Fr::<G>::zero(); *srs_lengths.iter().max().unwrap_or(&0)];
 
-        let padded_length = 1 << max_rounds;
-
-        // TODO: This will need adjusting
-        let padding = padded_length - nonzero_length;
         let mut points = vec![self.h];
-        points.extend(self.g.clone());
-        points.extend(vec![G::zero(); padding]);
-
-        let mut scalars = vec![Fr::<G>::zero(); padded_length + 1];
-        assert_eq!(scalars.len(), points.len());
+        let mut scalars = vec![Fr::<G>::zero()];
 
         // sample randomiser to scale the proofs with
         let rand_base = Fr::<G>::rand(rng);
@@ -719,62 +1438,34 @@ where
         let mut rand_base_i = Fr::<G>::one();
         let mut sg_rand_base_i = Fr::<G>::one();
 
-        for (sponge, evaluation_points, xi, r, polys, opening) in batch.iter_mut() {
-            // TODO: This computation is repeated in ProverProof::oracles
-            let combined_inner_product0 = {
-                let es: Vec<_> = polys
-                    .iter()
-                    .map(|(comm, evals, bound)| {
-                        let bound: Option<usize> = (|| {
-                            let b = (*bound)?;
-                            let x = comm.shifted?;
-                            if x.is_zero() {
-                                None
-                            } else {
-                                Some(b)
-                            }
-                        })();
-                        (evals.clone(), bound)
-                    })
-                    .collect();
-                combined_inner_product::<G>(evaluation_points, xi, r, &es, self.g.len())
-            };
-
-            sponge.absorb_fr(&[shift_scalar(combined_inner_product0)]);
-
-            let t = sponge.challenge_fq();
-            let u: G = to_group(group_map, t);
-
-            let Challenges { chal, chal_inv } =
-                opening.challenges::<EFqSponge>(&self.endo_r, sponge);
-
-            sponge.absorb_g(&[opening.delta]);
-            let c = ScalarChallenge(sponge.challenge()).to_field(&self.endo_r);
-
-            // < s, sum_i r^i pows(evaluation_point[i]) >
-            // ==
-            // sum_i r^i < s, pows(evaluation_point[i]) >
-            let b0 = {
-                let mut scale = Fr::<G>::one();
-                let mut res = Fr::<G>::zero();
-                for &e in evaluation_points.iter() {
-                    let term = b_poly(&chal, e);
-                    res += &(scale * &term);
-                    scale *= *r;
-                }
-                res
+        for ((sponge, evaluation_points, xi, r, polys, opening), srs_length) in
+            batch.iter_mut().zip(srs_lengths.iter())
+        {
+            let chal = match self.ipa_terms(
+                *srs_length,
+                group_map,
+                sponge,
+                evaluation_points,
+                *xi,
+                *r,
+                polys,
+                opening,
+                rand_base_i,
+                &mut points,
+                &mut scalars,
+            ) {
+                Some(chal) => chal,
+                None => return false,
             };
 
             let s = b_poly_coefficients(&chal);
 
-            let neg_rand_base_i = -rand_base_i;
-
             // TERM
-            // - rand_base_i z1 G
+            // - sg_rand_base_i * G
             //
-            // we also add -sg_rand_base_i * G to check correctness of sg.
+            // to check correctness of sg.
             points.push(opening.sg);
-            scalars.push(neg_rand_base_i * &opening.z1 - &sg_rand_base_i);
+            scalars.push(-sg_rand_base_i);
 
             // Here we add
             // sg_rand_base_i * ( < s, self.g > )
@@ -782,77 +1473,309 @@ where
             // < sg_rand_base_i s, self.g >
             //
             // to check correctness of the sg component.
+            // The generators past the SRS of the proof were padded with zero by the prover
             {
-                let terms: Vec<_> = s.par_iter().map(|s| sg_rand_base_i * s).collect();
+                let terms: Vec<_> = s[0..min(s.len(), *srs_length)]
+                    .par_iter()
+                    .map(|s| sg_rand_base_i * s)
+                    .collect();
 
                 for (i, term) in terms.iter().enumerate() {
-                    scalars[i + 1] += term;
+                    g_scalars[i] += term;
                 }
             }
 
-            // TERM
-            // - rand_base_i * z2 * H
-            scalars[0] -= &(rand_base_i * &opening.z2);
+            rand_base_i *= &rand_base;
+            sg_rand_base_i *= &sg_rand_base;
+        }
+        // verify the equation
+        self.g_msm(0, &g_scalars) + &self.msm(&points, &scalars) == G::Projective::zero()
+    }
 
-            // TERM
-            // -rand_base_i * (z1 * b0 * U)
-            scalars.push(neg_rand_base_i * &(opening.z1 * &b0));
-            points.push(u);
+    // This function verifies batch of batched polynomial commitment opening proofs as
+    // verify_prefixes does and, when the batch fails, bisects it to find the failing proofs
+    //     batch: batch of batched polynomial commitment opening proofs, as in verify
+    //     srs_lengths: lengths of the SRS instances the proofs of the batch were made against
+    //     randomness source context
+    //     RETURN: indices of the failing proofs with the check they fail, empty if the batch verifies
+    pub fn verify_failures<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        batch: &Vec<(
+            EFqSponge,
+            Vec<Fr<G>>, // vector of evaluation points
+            Fr<G>,      // scaling factor for polynoms
+            Fr<G>,      // scaling factor for evaluation point powers
+            Vec<(
+                &PolyComm<G>,     // polycommitment
+                Vec<&Vec<Fr<G>>>, // vector of evaluations
+                Option<usize>,    // optional degree bound
+            )>,
+            &OpeningProof<G>, // batched opening proof
+        )>,
+        srs_lengths: &[usize],
+        rng: &mut dyn RngCore,
+    ) -> Vec<(usize, FailedCheck)> {
+        let mut failures = vec![];
+        let mut pending = vec![(0..batch.len()).collect::<Vec<_>>()];
 
-            // TERM
-            // rand_base_i c_i Q_i
-            // = rand_base_i c_i
-            //   (sum_j (chal_invs[j] L_j + chals[j] R_j) + P_prime)
-            // where P_prime = combined commitment + combined_inner_product * U
-            let rand_base_i_c_i = c * &rand_base_i;
-            for ((l, r), (u_inv, u)) in opening.lr.iter().zip(chal_inv.iter().zip(chal.iter())) {
-                points.push(*l);
-                scalars.push(rand_base_i_c_i * u_inv);
-
-                points.push(*r);
-                scalars.push(rand_base_i_c_i * u);
+        while let Some(indices) = pending.pop() {
+            if indices.is_empty() {
+                continue;
+            }
+            let mut sub = indices.iter().map(|i| batch[*i].clone()).collect();
+            let lengths: Vec<_> = indices.iter().map(|i| srs_lengths[*i]).collect();
+            if self.verify_prefixes::<EFqSponge>(group_map, &mut sub, &lengths, rng) {
+                continue;
             }
 
-            // TERM
-            // sum_j r^j (sum_i xi^i f_i) (elm_j)
-            // == sum_j sum_i r^j xi^i f_i(elm_j)
-            // == sum_i xi^i sum_j r^j f_i(elm_j)
-            {
-                let mut xi_i = Fr::<G>::one();
-
-                for (comm, _evals_tr, shifted) in polys.iter().filter(|x| x.0.unshifted.len() > 0) {
-                    // iterating over the polynomial segments
-                    for comm_ch in comm.unshifted.iter() {
-                        scalars.push(rand_base_i_c_i * &xi_i);
-                        points.push(*comm_ch);
-                        xi_i *= *xi;
-                    }
+            if indices.len() == 1 {
+                // the failing proof either does not satisfy the IPA equation with its own sg,
+                // or its sg is not the commitment to the polynomial of its challenges
+                let (mut sponge, evaluation_points, xi, r, polys, opening) = sub.remove(0);
+                let mut points = vec![self.h];
+                let mut scalars = vec![Fr::<G>::zero()];
+                let succinct = self
+                    .ipa_terms(
+                        lengths[0],
+                        group_map,
+                        &mut sponge,
+                        &evaluation_points,
+                        xi,
+                        r,
+                        &polys,
+                        opening,
+                        Fr::<G>::one(),
+                        &mut points,
+                        &mut scalars,
+                    )
+                    .map(|_| self.msm(&points, &scalars) == G::Projective::zero())
+                    .unwrap_or(false);
+                failures.push((
+                    indices[0],
+                    if succinct {
+                        FailedCheck::Sg
+                    } else {
+                        FailedCheck::Ipa
+                    },
+                ));
+            } else {
+                let (lo, hi) = indices.split_at(indices.len() / 2);
+                pending.push(hi.to_vec());
+                pending.push(lo.to_vec());
+            }
+        }
+        failures
+    }
 
-                    if let Some(_m) = shifted {
-                        if let Some(comm_ch) = comm.shifted {
-                            if comm_ch.is_zero() == false {
-                                // xi^i sum_j r^j elm_j^{N - m} f(elm_j)
-                                scalars.push(rand_base_i_c_i * &xi_i);
-                                points.push(comm_ch);
-                                xi_i *= *xi;
-                            }
-                        }
-                    }
-                }
-            };
+    // This function performs the succinct part of the verification of a batch of batched
+    // polynomial commitment opening proofs. The linear time check that the sg component of
+    // every proof is the commitment to the polynomial b defined by its challenges is deferred.
+    //     batch: batch of batched polynomial commitment opening proofs, as in verify
+    //     randomness source context
+    //     RETURN: accumulators of the deferred checks, None if the succinct check fails
+    pub fn succinct_verify<EFqSponge: FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut Vec<(
+            EFqSponge,
+            Vec<Fr<G>>, // vector of evaluation points
+            Fr<G>,      // scaling factor for polynoms
+            Fr<G>,      // scaling factor for evaluation point powers
+            Vec<(
+                &PolyComm<G>,     // polycommitment
+                Vec<&Vec<Fr<G>>>, // vector of evaluations
+                Option<usize>,    // optional degree bound
+            )>,
+            &OpeningProof<G>, // batched opening proof
+        )>,
+        rng: &mut dyn RngCore,
+    ) -> Option<Vec<Accumulator<G>>> {
+        // Same equation as in verify, but G_i is taken to be the sg component of
+        // the proof, so that the multiexp only involves points of the proofs
+        let mut points = vec![self.h];
+        let mut scalars = vec![Fr::<G>::zero()];
 
-            scalars.push(rand_base_i_c_i * &combined_inner_product0);
-            points.push(u);
+        let rand_base = Fr::<G>::rand(rng);
+        let mut rand_base_i = Fr::<G>::one();
 
-            scalars.push(rand_base_i);
-            points.push(opening.delta);
+        let mut accumulators = vec![];
+
+        for (sponge, evaluation_points, xi, r, polys, opening) in batch.iter_mut() {
+            let chals = self.ipa_terms(
+                self.g.len(),
+                group_map,
+                sponge,
+                evaluation_points,
+                *xi,
+                *r,
+                polys,
+                opening,
+                rand_base_i,
+                &mut points,
+                &mut scalars,
+            )?;
+            accumulators.push(Accumulator {
+                chals,
+                sg: opening.sg,
+            });
 
             rand_base_i *= &rand_base;
-            sg_rand_base_i *= &sg_rand_base;
         }
-        // verify the equation
-        let scalars: Vec<_> = scalars.iter().map(|x| x.into_repr()).collect();
-        VariableBaseMSM::multi_scalar_mul(&points, &scalars) == G::Projective::zero()
+
+        if self.msm(&points, &scalars) == G::Projective::zero() {
+            Some(accumulators)
+        } else {
+            None
+        }
+    }
+
+    // This function decides a batch of accumulators with a single multiexp over the SRS
+    //     accumulators: deferred checks that sg = < s, self.g > where s are the coefficients of b
+    //     randomness source context
+    //     RETURN: verification status
+    pub fn check_accumulators(
+        &self,
+        accumulators: &[Accumulator<G>],
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        // sum_i r^i (< s_i, self.g > - sg_i) == 0
+        let mut points = self.g.clone();
+        let mut scalars = vec![Fr::<G>::zero(); self.g.len()];
+
+        let rand_base = Fr::<G>::rand(rng);
+        let mut rand_base_i = Fr::<G>::one();
+
+        for acc in accumulators.iter() {
+            // openings may be trimmed to a prefix of SRS, but never exceed it
+            if acc.chals.len() > ceil_log2(self.g.len()) {
+                return false;
+            }
+            let s = b_poly_coefficients(&acc.chals);
+            let terms: Vec<_> = s[0..min(s.len(), self.g.len())]
+                .par_iter()
+                .map(|s| rand_base_i * s)
+                .collect();
+            for (i, term) in terms.iter().enumerate() {
+                scalars[i] += term;
+            }
+
+            points.push(acc.sg);
+            scalars.push(-rand_base_i);
+
+            rand_base_i *= &rand_base;
+        }
+
+        self.msm(&points, &scalars) == G::Projective::zero()
+    }
+
+    // This function adds to the multiexp the terms of the opening proof equation
+    //     c Q + delta = z1 (sg + b0 U) + z2 H
+    // scaled by rand_base_i, where the blinding base H is points[0]
+    //     srs_length: length of the prefix of SRS the proof was made against
+    //     RETURN: the challenges of the opening proof, None if the proof has more rounds than SRS
+    fn ipa_terms<EFqSponge: FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        srs_length: usize,
+        group_map: &G::Map,
+        sponge: &mut EFqSponge,
+        evaluation_points: &Vec<Fr<G>>,
+        xi: Fr<G>,
+        r: Fr<G>,
+        polys: &Vec<(&PolyComm<G>, Vec<&Vec<Fr<G>>>, Option<usize>)>,
+        opening: &OpeningProof<G>,
+        rand_base_i: Fr<G>,
+        points: &mut Vec<G>,
+        scalars: &mut Vec<Fr<G>>,
+    ) -> Option<Vec<Fr<G>>> {
+        // the proof may open against a prefix of SRS of any length up to the padded SRS
+        if opening.lr.len() > ceil_log2(srs_length) {
+            return None;
+        }
+
+        // TODO: This computation is repeated in ProverProof::oracles
+        let combined_inner_product0 = {
+            let es: Vec<_> = polys
+                .iter()
+                .map(|(comm, evals, bound)| {
+                    let bound = if comm.shifted.is_some() { *bound } else { None };
+                    (evals.clone(), bound)
+                })
+                .collect();
+            combined_inner_product::<G>(evaluation_points, &xi, &r, &es, srs_length)
+        };
+
+        sponge.label("opening");
+        sponge.absorb_fr(&[shift_scalar(combined_inner_product0)]);
+
+        let t = sponge.challenge_fq();
+        let u: G = to_group(group_map, t);
+
+        let Challenges { chal, chal_inv } = opening.challenges::<EFqSponge>(&self.endo_r, sponge);
+
+        sponge.absorb_g(&[opening.delta]);
+        let c = ScalarChallenge(sponge.challenge()).to_field(&self.endo_r);
+
+        // < s, sum_i r^i pows(evaluation_point[i]) >
+        // ==
+        // sum_i r^i < s, pows(evaluation_point[i]) >
+        let b0 = {
+            let mut scale = Fr::<G>::one();
+            let mut res = Fr::<G>::zero();
+            for &e in evaluation_points.iter() {
+                let term = b_poly(&chal, e);
+                res += &(scale * &term);
+                scale *= r;
+            }
+            res
+        };
+
+        let neg_rand_base_i = -rand_base_i;
+
+        // TERM
+        // - rand_base_i z1 G
+        points.push(opening.sg);
+        scalars.push(neg_rand_base_i * &opening.z1);
+
+        // TERM
+        // - rand_base_i * z2 * H
+        scalars[0] -= &(rand_base_i * &opening.z2);
+
+        // TERM
+        // -rand_base_i * (z1 * b0 * U)
+        scalars.push(neg_rand_base_i * &(opening.z1 * &b0));
+        points.push(u);
+
+        // TERM
+        // rand_base_i c_i Q_i
+        // = rand_base_i c_i
+        //   (sum_j (chal_invs[j] L_j + chals[j] R_j) + P_prime)
+        // where P_prime = combined commitment + combined_inner_product * U
+        let rand_base_i_c_i = c * &rand_base_i;
+        for ((l, r), (u_inv, u)) in opening.lr.iter().zip(chal_inv.iter().zip(chal.iter())) {
+            points.push(*l);
+            scalars.push(rand_base_i_c_i * u_inv);
+
+            points.push(*r);
+            scalars.push(rand_base_i_c_i * u);
+        }
+
+        // TERM
+        // sum_j r^j (sum_i xi^i f_i) (elm_j)
+        // == sum_j sum_i r^j xi^i f_i(elm_j)
+        // == sum_i xi^i sum_j r^j f_i(elm_j)
+        for (comm_ch, xi_i) in combined_commitment_terms(polys, xi) {
+            scalars.push(rand_base_i_c_i * &xi_i);
+            points.push(comm_ch);
+        }
+
+        scalars.push(rand_base_i_c_i * &combined_inner_product0);
+        points.push(u);
+
+        scalars.push(rand_base_i);
+        points.push(opening.delta);
+
+        Some(chal)
     }
 }
 
@@ -898,7 +1821,11 @@ impl<F: Field> Utils<F> for DensePolynomial<F> {
     }
 
     // This function evaluates polynomial in chunks
+    // The zero polynomial has a single zero chunk, matching its hiding commitment
     fn eval(&self, elm: F, size: usize) -> Vec<F> {
+        if self.coeffs.is_empty() {
+            return vec![F::zero()];
+        }
         (0..self.coeffs.len())
             .step_by(size)
             .map(|i| {
@@ -0,0 +1,190 @@
+/*****************************************************************************************************************
+
+This source file implements the fixed-base multi-scalar multiplication over precomputed
+tables of the multiples of the bases
+
+For the window size w, the table of a base P holds the multiples 2^(w * j) * P for every
+w-bit window j of the scalars. A multi-scalar multiplication over the bases is then a
+multi-scalar multiplication of w-bit digits, computed with buckets and without doublings.
+The table takes ceil(MODULUS_BITS / w) points per base, so larger windows take less memory
+while the bucket accumulation costs 2^w additions per thread
+
+*****************************************************************************************************************/
+
+use crate::serialization::{
+    read_header, read_len, read_points, write_header, write_len, write_points,
+};
+use algebra::{
+    AffineCurve, BigInteger, Field, FpParameters, PrimeField, ProjectiveCurve, UniformRand,
+    VariableBaseMSM, Zero,
+};
+use rand_core::RngCore;
+use rayon::prelude::*;
+use std::cmp::max;
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
+
+const TABLE_MAGIC: &[u8; 4] = b"FBTB";
+const MAX_WINDOW: usize = 16;
+
+type BigInt<G> = <<G as AffineCurve>::ScalarField as PrimeField>::BigInt;
+
+#[derive(Debug, Clone)]
+pub struct FixedBaseTable<G: AffineCurve> {
+    pub window: usize,
+    // multiples[i][j] = 2^(window * j) * bases[i]
+    pub multiples: Vec<Vec<G>>,
+}
+
+impl<G: AffineCurve> FixedBaseTable<G> {
+    // This function computes the tables of the bases
+    //     bases: the fixed bases
+    //     window: window size in bits
+    pub fn create(bases: &[G], window: usize) -> Self {
+        assert!(window > 0 && window <= MAX_WINDOW);
+        let bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let windows = (bits + window - 1) / window;
+
+        let multiples = bases
+            .par_iter()
+            .map(|base| {
+                let mut p = base.into_projective();
+                let mut v = Vec::with_capacity(windows);
+                for _ in 0..windows {
+                    v.push(p);
+                    for _ in 0..window {
+                        p.double_in_place();
+                    }
+                }
+                G::Projective::batch_normalization(v.as_mut_slice());
+                v.iter().map(|x| x.into_affine()).collect()
+            })
+            .collect();
+
+        FixedBaseTable { window, multiples }
+    }
+
+    // This function computes the multi-scalar multiplication over a range of the bases
+    //     offset: index of the base the first scalar multiplies
+    //     scalars: scalars multiplying the bases from offset on
+    //     RETURN: sum_i scalars[i] * bases[offset + i]
+    pub fn msm(&self, offset: usize, scalars: &[BigInt<G>]) -> G::Projective {
+        assert!(offset + scalars.len() <= self.multiples.len());
+        let window = self.window;
+        let chunk = max(1, scalars.len() / rayon::current_num_threads());
+
+        self.multiples[offset..offset + scalars.len()]
+            .par_chunks(chunk)
+            .zip(scalars.par_chunks(chunk))
+            .map(|(multiples, scalars)| {
+                // buckets[d - 1] accumulates the multiples the digit d multiplies
+                let mut buckets = vec![G::Projective::zero(); (1 << window) - 1];
+                for (m, s) in multiples.iter().zip(scalars.iter()) {
+                    for (j, mj) in m.iter().enumerate() {
+                        let d = digit(s, j * window, window);
+                        if d != 0 {
+                            buckets[d - 1].add_assign_mixed(mj);
+                        }
+                    }
+                }
+
+                // sum_d d * buckets[d - 1], as a sum of the running sums from the top bucket
+                let mut running = G::Projective::zero();
+                let mut res = G::Projective::zero();
+                for b in buckets.iter().rev() {
+                    running += b;
+                    res += &running;
+                }
+                res
+            })
+            .reduce(G::Projective::zero, |a, b| a + &b)
+    }
+
+    // This function checks every multiple of the tables to be the previous one doubled window
+    // times, with a random linear combination of their differences
+    //     rng: randomness source context
+    //     RETURN: whether the multiples of every base are consistent
+    pub fn check_multiples(&self, rng: &mut dyn RngCore) -> bool {
+        let shift = G::ScalarField::from(2u64).pow(&[self.window as u64]);
+        let scalars = self
+            .multiples
+            .iter()
+            .map(|m| {
+                // sum_j rho_j (m[j + 1] - 2^window m[j]) is zero for consistent multiples
+                let rho: Vec<_> = (1..m.len()).map(|_| G::ScalarField::rand(rng)).collect();
+                (0..m.len())
+                    .map(|j| {
+                        let mut s = G::ScalarField::zero();
+                        if j > 0 {
+                            s += &rho[j - 1];
+                        }
+                        if j + 1 < m.len() {
+                            s -= &(shift * &rho[j]);
+                        }
+                        s.into_repr()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let chunk = max(1, self.multiples.len() / rayon::current_num_threads());
+        self.multiples
+            .par_chunks(chunk)
+            .zip(scalars.par_chunks(chunk))
+            .map(|(multiples, scalars)| {
+                VariableBaseMSM::multi_scalar_mul(&multiples.concat(), &scalars.concat())
+            })
+            .reduce(G::Projective::zero, |a, b| a + &b)
+            .is_zero()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_header(TABLE_MAGIC, &mut writer)?;
+        write_len(self.window, &mut writer)?;
+        write_len(self.multiples.len(), &mut writer)?;
+        for m in self.multiples.iter() {
+            write_points(m, &mut writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        read_header(TABLE_MAGIC, &mut reader)?;
+        let window = read_len(&mut reader)?;
+        if window == 0 || window > MAX_WINDOW {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid window size"));
+        }
+        let bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        let windows = (bits + window - 1) / window;
+
+        let mut multiples = vec![];
+        for _ in 0..read_len(&mut reader)? {
+            let m = read_points(&mut reader)?;
+            if m.len() != windows {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "Invalid number of multiples of a base",
+                ));
+            }
+            multiples.push(m);
+        }
+        Ok(FixedBaseTable { window, multiples })
+    }
+}
+
+// This function extracts the digit of a scalar made of the given bits
+//     s: scalar
+//     start: index of the least significant bit of the digit
+//     width: number of bits of the digit
+pub(crate) fn digit<B: BigInteger>(s: &B, start: usize, width: usize) -> usize {
+    let limbs = s.as_ref();
+    let mut d = 0;
+    for i in (start..start + width).rev() {
+        let limb = if i / 64 < limbs.len() {
+            limbs[i / 64]
+        } else {
+            0
+        };
+        d = (d << 1) | ((limb >> (i % 64)) & 1) as usize;
+    }
+    d
+}
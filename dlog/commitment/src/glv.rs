@@ -0,0 +1,334 @@
+/*****************************************************************************************************************
+
+This source file implements the multi-scalar multiplication exploiting the endomorphism
+(x, y) -> (endo_q * x, y) of the short Weierstrass curves with j-invariant 0, as the pasta curves
+
+The endomorphism multiplies the points of the prime order subgroup by endo_r, so a scalar
+k = k1 + k2 * endo_r with k1, k2 of half the width of the scalar field gives
+k * P = k1 * P + k2 * phi(P). The decomposition rounds k against a short basis of the lattice
+of the (x, y) such that x + y * endo_r = 0 mod r, computed with the extended Euclidean algorithm.
+The multi-scalar multiplication of the half-width scalars then accumulates the points of every
+bucket with the batch-affine additions of combine.rs
+
+*****************************************************************************************************************/
+
+use crate::combine::add_pairs_in_place;
+use crate::fixed_base::digit;
+use algebra::{
+    curves::models::short_weierstrass_jacobian::{GroupAffine as SWJAffine, GroupProjective},
+    AffineCurve, BigInteger, FpParameters, One, PrimeField, ProjectiveCurve, SWModelParameters,
+    Zero,
+};
+use rayon::prelude::*;
+use std::cmp::{min, Ordering};
+use std::fmt;
+
+type BigInt<F> = <F as PrimeField>::BigInt;
+
+pub struct Glv<P: SWModelParameters> {
+    endo_q: P::BaseField,
+    // short basis (a1, b1), (a2, b2) of the lattice of the (x, y) such that x + y * endo_r = 0,
+    // with a1, a2 >= 0 and b1, b2 of opposite signs
+    a1: P::ScalarField,
+    b1: P::ScalarField,
+    a2: P::ScalarField,
+    b2: P::ScalarField,
+    // floor(2^(64 * LIMBS) * |b2| / r) and floor(2^(64 * LIMBS) * |b1| / r)
+    g1: Vec<u64>,
+    g2: Vec<u64>,
+}
+
+impl<P: SWModelParameters> Clone for Glv<P> {
+    fn clone(&self) -> Self {
+        Glv {
+            endo_q: self.endo_q,
+            a1: self.a1,
+            b1: self.b1,
+            a2: self.a2,
+            b2: self.b2,
+            g1: self.g1.clone(),
+            g2: self.g2.clone(),
+        }
+    }
+}
+
+impl<P: SWModelParameters> fmt::Debug for Glv<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Glv")
+            .field("endo_q", &self.endo_q)
+            .field("a1", &self.a1)
+            .field("b1", &self.b1)
+            .field("a2", &self.a2)
+            .field("b2", &self.b2)
+            .finish()
+    }
+}
+
+impl<P: SWModelParameters> Glv<P> {
+    // This function computes the decomposition basis for the endomorphism
+    //     endo_q: base field coefficient of the endomorphism
+    //     endo_r: scalar field eigenvalue of the endomorphism
+    //     RETURN: the decomposition basis, None if endo_r is not the eigenvalue of the
+    //         endomorphism of endo_q, as on the curves of j-invariant other than 0
+    pub fn new(endo_q: P::BaseField, endo_r: P::ScalarField) -> Option<Self> {
+        // the decomposition is only correct for endo_r the eigenvalue of the endomorphism of
+        // endo_q, which holds on the curves with j-invariant 0 for the pair endos returns
+        let g = SWJAffine::<P>::prime_subgroup_generator();
+        if SWJAffine::<P>::new(g.x * &endo_q, g.y, false).into_projective() != g.mul(endo_r) {
+            return None;
+        }
+        let n = <P::ScalarField as PrimeField>::Params::MODULUS
+            .as_ref()
+            .to_vec();
+
+        // r_i = s_i * n + t_i * endo_r, up to the first remainder below sqrt(n) and the next one
+        let (mut r0, mut r1) = (n.clone(), endo_r.into_repr().as_ref().to_vec());
+        let (mut t0, mut t1) = (P::ScalarField::zero(), P::ScalarField::one());
+        while cmp(&mul(&r1, &r1), &n) != Ordering::Less {
+            let (q, r2) = div_rem(&r0, &r1);
+            let t2 = t0 - &(of_limbs::<P::ScalarField>(&q) * &t1);
+            r0 = r1;
+            r1 = r2;
+            t0 = t1;
+            t1 = t2;
+        }
+        let (q, r2) = div_rem(&r0, &r1);
+        let t2 = t0 - &(of_limbs::<P::ScalarField>(&q) * &t1);
+
+        // (a2, b2) is the shortest of (r_l, -t_l) and (r_{l+2}, -t_{l+2})
+        let norm = |r: &[u64], t: P::ScalarField| {
+            let t = magnitude(t).1;
+            add(&mul(r, r), &mul(t.as_ref(), t.as_ref()))
+        };
+        let (a2, b2) = if cmp(&norm(&r0, t0), &norm(&r2, t2)) == Ordering::Greater {
+            (r2, -t2)
+        } else {
+            (r0, -t0)
+        };
+        let (a1, b1) = (r1, -t1);
+
+        let shift = |b: P::ScalarField| {
+            let mut x = vec![0u64; n.len()];
+            x.extend(magnitude(b).1.as_ref());
+            div_rem(&x, &n).0
+        };
+        Some(Glv {
+            endo_q,
+            g1: shift(b2),
+            g2: shift(b1),
+            a1: of_limbs(&a1),
+            b1,
+            a2: of_limbs(&a2),
+            b2,
+        })
+    }
+
+    // This function decomposes a scalar into half-width scalars
+    //     k: scalar
+    //     RETURN: signs and magnitudes of k1, k2 such that k = k1 + k2 * endo_r
+    pub fn decompose(
+        &self,
+        k: P::ScalarField,
+    ) -> (
+        (bool, BigInt<P::ScalarField>),
+        (bool, BigInt<P::ScalarField>),
+    ) {
+        let limbs = <P::ScalarField as PrimeField>::Params::MODULUS
+            .as_ref()
+            .len();
+        let repr = k.into_repr();
+        // c1 = round(b2 * k / det), c2 = round(-b1 * k / det) where det = a1 * b2 - a2 * b1
+        // has the sign of b2, approximated with the precomputed quotients
+        let c1 = of_limbs::<P::ScalarField>(&mul(repr.as_ref(), &self.g1)[limbs..]);
+        let c2 = of_limbs::<P::ScalarField>(&mul(repr.as_ref(), &self.g2)[limbs..]);
+
+        // k1 + k2 * endo_r = k whatever c1 and c2, as a1 + b1 * endo_r = a2 + b2 * endo_r = 0
+        let k1 = k - &(c1 * &self.a1) - &(c2 * &self.a2);
+        let k2 = -(c1 * &self.b1) - &(c2 * &self.b2);
+        (magnitude(k1), magnitude(k2))
+    }
+
+    // This function computes the multi-scalar multiplication. As with VariableBaseMSM,
+    // the bases or scalars past the length of the other vector are ignored
+    //     bases: points of the prime order subgroup
+    //     scalars: scalars multiplying the bases
+    //     RETURN: sum_i scalars[i] * bases[i]
+    pub fn msm(&self, bases: &[SWJAffine<P>], scalars: &[P::ScalarField]) -> GroupProjective<P> {
+        let n = min(bases.len(), scalars.len());
+        let terms: Vec<(SWJAffine<P>, BigInt<P::ScalarField>)> = bases[0..n]
+            .par_iter()
+            .zip(scalars[0..n].par_iter())
+            .flat_map(|(g, k)| {
+                let ((neg1, k1), (neg2, k2)) = self.decompose(*k);
+                let phi = if g.infinity {
+                    *g
+                } else {
+                    SWJAffine::<P>::new(g.x * &self.endo_q, g.y, false)
+                };
+                vec![
+                    (if neg1 { -*g } else { *g }, k1),
+                    (if neg2 { -phi } else { phi }, k2),
+                ]
+            })
+            .collect();
+
+        let bits = terms
+            .iter()
+            .map(|(_, k)| k.num_bits() as usize)
+            .max()
+            .unwrap_or(0);
+        let c = if terms.len() < 32 {
+            3
+        } else {
+            (terms.len() as f64).ln().ceil() as usize + 2
+        };
+
+        let windows: Vec<GroupProjective<P>> = (0..(bits + c - 1) / c)
+            .into_par_iter()
+            .map(|w| {
+                let mut buckets = vec![vec![]; (1 << c) - 1];
+                for (g, k) in terms.iter() {
+                    let d = digit(k, w * c, c);
+                    if d != 0 && !g.infinity {
+                        buckets[d - 1].push(*g);
+                    }
+                }
+
+                // sum_d d * buckets[d - 1], as a sum of the running sums from the top bucket
+                let mut running = GroupProjective::<P>::zero();
+                let mut res = GroupProjective::<P>::zero();
+                for b in sum_buckets(buckets).iter().rev() {
+                    running.add_assign_mixed(b);
+                    res += &running;
+                }
+                res
+            })
+            .collect();
+
+        let mut res = GroupProjective::<P>::zero();
+        for window in windows.iter().rev() {
+            for _ in 0..c {
+                res.double_in_place();
+            }
+            res += window;
+        }
+        res
+    }
+}
+
+// This function sums the points of every bucket, every round pairing the points of the buckets
+// holding several of them and adding the pairs of all the buckets with a single batch inversion
+fn sum_buckets<P: SWModelParameters>(mut buckets: Vec<Vec<SWJAffine<P>>>) -> Vec<SWJAffine<P>> {
+    while buckets.iter().any(|b| b.len() > 1) {
+        let mut v = vec![];
+        let mut halves = vec![];
+        for (i, b) in buckets.iter_mut().enumerate().filter(|(_, b)| b.len() > 1) {
+            if b.len() % 2 == 1 {
+                b.push(SWJAffine::<P>::zero());
+            }
+            halves.push((i, b.len() / 2));
+            v.append(b);
+        }
+        add_pairs_in_place(&mut v);
+
+        let mut sums = v.into_iter();
+        for (i, len) in halves {
+            buckets[i] = sums.by_ref().take(len).collect();
+        }
+    }
+    buckets
+        .into_iter()
+        .map(|b| b.first().copied().unwrap_or_else(SWJAffine::<P>::zero))
+        .collect()
+}
+
+// This function returns the sign and the magnitude of the integer of absolute value below r / 2
+// congruent to the field element
+fn magnitude<F: PrimeField>(x: F) -> (bool, F::BigInt) {
+    if x.into_repr() > F::Params::MODULUS_MINUS_ONE_DIV_TWO {
+        (true, (-x).into_repr())
+    } else {
+        (false, x.into_repr())
+    }
+}
+
+// This function converts little-endian limbs of an integer below the modulus to a field element
+fn of_limbs<F: PrimeField>(x: &[u64]) -> F {
+    let mut repr = F::BigInt::default();
+    let len = repr.as_ref().len();
+    assert!(x.iter().skip(len).all(|l| *l == 0));
+    for (r, l) in repr.as_mut().iter_mut().zip(x.iter()) {
+        *r = *l;
+    }
+    F::from_repr(repr)
+}
+
+fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    let limb = |x: &[u64], i: usize| if i < x.len() { x[i] } else { 0 };
+    (0..a.len().max(b.len()))
+        .rev()
+        .map(|i| limb(a, i).cmp(&limb(b, i)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn add(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let limb = |x: &[u64], i: usize| if i < x.len() { x[i] as u128 } else { 0 };
+    let n = a.len().max(b.len());
+    let mut res = vec![0u64; n + 1];
+    let mut carry = 0u128;
+    for i in 0..n {
+        let t = limb(a, i) + limb(b, i) + carry;
+        res[i] = t as u64;
+        carry = t >> 64;
+    }
+    res[n] = carry as u64;
+    res
+}
+
+fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = vec![0u64; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, y) in b.iter().enumerate() {
+            let t = (*x as u128) * (*y as u128) + res[i + j] as u128 + carry;
+            res[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        res[i + b.len()] = carry as u64;
+    }
+    res
+}
+
+// This function divides integers given by their little-endian limbs, bit by bit
+//     RETURN: quotient and remainder
+fn div_rem(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(b.iter().any(|l| *l != 0));
+    let mut q = vec![0u64; a.len()];
+    let mut r = vec![0u64; b.len() + 1];
+    for i in (0..64 * a.len()).rev() {
+        // r = 2 * r + bit i of a
+        for j in (1..r.len()).rev() {
+            r[j] = (r[j] << 1) | (r[j - 1] >> 63);
+        }
+        r[0] = (r[0] << 1) | ((a[i / 64] >> (i % 64)) & 1);
+
+        if cmp(&r, b) != Ordering::Less {
+            let mut borrow = 0u128;
+            for j in 0..r.len() {
+                let y = if j < b.len() { b[j] as u128 } else { 0 } + borrow;
+                let x = r[j] as u128;
+                if x >= y {
+                    r[j] = (x - y) as u64;
+                    borrow = 0;
+                } else {
+                    r[j] = ((1u128 << 64) + x - y) as u64;
+                    borrow = 1;
+                }
+            }
+            q[i / 64] |= 1 << (i % 64);
+        }
+    }
+    r.truncate(b.len());
+    (q, r)
+}
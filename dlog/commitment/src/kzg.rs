@@ -0,0 +1,234 @@
+/*****************************************************************************************************************
+
+This source file implements the KZG polynomial commitment scheme over a pairing curve
+
+The SRS holds the powers of a secret tau in G1, and tau in G2. The commitments are segmented
+as the commitments of the inner product argument, so that both schemes combine and evaluate the
+segments the same way. The polynomials are opened by combining the batch with the polynomial
+scaling factor and committing, for every evaluation point x, to the quotient
+W(X) = (P(X) - P(x)) / (X - x) of the combined polynomial P. The verifier checks
+e(C - P(x) G + x W, H) = e(W, tau H) for all the points of all the proofs of a batch at once,
+with random scaling factors.
+
+The commitments and the openings are not hiding. The plonk provers of this repository do not blind
+the witness polynomials and rely on the commitments being hiding for zero-knowledge, so that their
+proofs made with this scheme are sound but not zero-knowledge. The Fq sponge and the group map
+the plonk provers and verifiers pass to the openings are ignored. SRS created by create knows
+its secret, so that it is only suitable for testing; production SRS comes from a setup ceremony.
+
+*****************************************************************************************************************/
+
+use crate::commitment::{
+    combined_commitment_terms, combined_inner_product, combined_polynomial, CommitmentCurve,
+    PolyComm,
+};
+use crate::scheme::{Batch, PolynomialCommitment};
+use crate::segments::commit_segments;
+use crate::validation::{check_points, InvalidPoint};
+use algebra::{
+    AffineCurve, Field, One, PairingEngine, PrimeField, ProjectiveCurve, UniformRand,
+    VariableBaseMSM, Zero,
+};
+use ff_fft::DensePolynomial;
+use oracle::FqSponge;
+use rand_core::RngCore;
+use rayon::prelude::*;
+use std::cmp::min;
+
+#[derive(Debug, Clone)]
+pub struct SRS<E: PairingEngine> {
+    pub g: Vec<E::G1Affine>, // powers of tau in G1
+    pub h: E::G2Affine,      // generator of G2
+    pub h_tau: E::G2Affine,  // tau in G2
+}
+
+#[derive(Debug, Clone)]
+pub struct KzgProof<E: PairingEngine> {
+    pub quotients: Vec<E::G1Affine>, // commitments to the quotients, one per evaluation point
+}
+
+impl<E: PairingEngine> SRS<E> {
+    // This function creates SRS instance from a random secret, for testing
+    //     depth: number of powers of the secret
+    pub fn create(depth: usize, rng: &mut dyn RngCore) -> Self {
+        let tau = E::Fr::rand(rng);
+        let mut powers = vec![E::Fr::one(); depth];
+        for i in 1..depth {
+            powers[i] = powers[i - 1] * &tau;
+        }
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let mut g: Vec<E::G1Projective> = powers.par_iter().map(|t| g1.mul(*t)).collect();
+        E::G1Projective::batch_normalization(g.as_mut_slice());
+
+        let h = E::G2Affine::prime_subgroup_generator();
+        SRS {
+            g: g.iter().map(|p| p.into_affine()).collect(),
+            h,
+            h_tau: h.mul(tau).into_affine(),
+        }
+    }
+
+    fn msm(&self, offset: usize, coeffs: &[E::Fr]) -> E::G1Projective {
+        let n = min(coeffs.len(), self.g.len() - offset);
+        let scalars: Vec<_> = coeffs[0..n].iter().map(|c| c.into_repr()).collect();
+        VariableBaseMSM::multi_scalar_mul(&self.g[offset..offset + n], &scalars)
+    }
+}
+
+impl<E: PairingEngine> PolynomialCommitment for SRS<E>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    type Curve = E::G1Affine;
+    type Proof = KzgProof<E>;
+
+    fn segment_size(&self) -> usize {
+        self.g.len()
+    }
+
+    // The powers of the same secret with the same generators
+    fn is_prefix_of(&self, other: &Self) -> bool {
+        self.h == other.h && self.h_tau == other.h_tau && other.g.starts_with(&self.g)
+    }
+
+    // The commitments are not hiding, the commitment randomness is zero. As with the inner
+    // product argument, the zero polynomial is committed as a single segment
+    fn commit(
+        &self,
+        plnm: &DensePolynomial<E::Fr>,
+        max: Option<usize>,
+        _rng: &mut dyn RngCore,
+    ) -> (PolyComm<E::G1Affine>, PolyComm<E::Fr>) {
+        let mut c = self.commit_non_hiding(plnm, max);
+        if c.unshifted.is_empty() {
+            c.unshifted.push(E::G1Affine::zero());
+        }
+        let w = PolyComm {
+            unshifted: vec![E::Fr::zero(); c.unshifted.len()],
+            shifted: c.shifted.map(|_| E::Fr::zero()),
+        };
+        (c, w)
+    }
+
+    fn commit_non_hiding(
+        &self,
+        plnm: &DensePolynomial<E::Fr>,
+        max: Option<usize>,
+    ) -> PolyComm<E::G1Affine> {
+        commit_segments(self.g.len(), plnm, max, &|offset, coeffs| {
+            self.msm(offset, coeffs)
+        })
+    }
+
+    // The proof has a quotient per evaluation point, so that evalscale is not used
+    fn open_maybe_hiding<EFqSponge: Clone + FqSponge<E::Fq, E::G1Affine, E::Fr>>(
+        &self,
+        _group_map: &<E::G1Affine as CommitmentCurve>::Map,
+        plnms: Vec<(&DensePolynomial<E::Fr>, Option<usize>, PolyComm<E::Fr>)>,
+        elm: &Vec<E::Fr>,
+        polyscale: E::Fr,
+        _evalscale: E::Fr,
+        _sponge: EFqSponge,
+        _hiding: bool,
+        _rng: &mut dyn RngCore,
+    ) -> KzgProof<E> {
+        let (p, _) = combined_polynomial(self.g.len(), &plnms, polyscale);
+        KzgProof {
+            quotients: elm
+                .par_iter()
+                .map(|x| self.msm(0, &quotient(&p, *x)).into_affine())
+                .collect(),
+        }
+    }
+
+    fn validate_proof(&self, proof: &KzgProof<E>) -> Result<(), InvalidPoint> {
+        check_points(&proof.quotients, "opening proof quotient")
+    }
+
+    // The proofs made against a prefix of SRS combine their degree bounded segments with the
+    // shift of the prefix, the pairing check is otherwise the same
+    fn verify_prefixes<EFqSponge: FqSponge<E::Fq, E::G1Affine, E::Fr>>(
+        &self,
+        _group_map: &<E::G1Affine as CommitmentCurve>::Map,
+        batch: &mut Batch<E::G1Affine, EFqSponge, KzgProof<E>>,
+        lengths: &[usize],
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        if lengths.len() != batch.len() || lengths.iter().any(|l| *l > self.g.len()) {
+            return false;
+        }
+
+        // for all the points x of all the proofs, with the combined commitment C, the combined
+        // evaluation v and the commitment to the quotient W,
+        // C - v G + x W = tau W
+        //
+        // if we sample rand at random, it suffices to check
+        //
+        // e(sum_i rand^i (C_i - v_i G + x_i W_i), H) = e(sum_i rand^i W_i, tau H)
+        let rand_base = E::Fr::rand(rng);
+        let mut rand_base_i = E::Fr::one();
+
+        // the left and the right multi-scalar multiplications, the first point of the left one
+        // being G
+        let mut points = vec![self.g[0]];
+        let mut scalars = vec![E::Fr::zero()];
+        let mut quotients = vec![];
+        let mut quotient_scalars = vec![];
+
+        for ((_, elm, xi, _, polys, proof), length) in batch.iter().zip(lengths.iter()) {
+            if proof.quotients.len() != elm.len()
+                || polys.iter().any(|(_, evals, _)| evals.len() != elm.len())
+            {
+                return false;
+            }
+            let terms = combined_commitment_terms(polys, *xi);
+
+            for (j, (x, w)) in elm.iter().zip(proof.quotients.iter()).enumerate() {
+                let es: Vec<_> = polys
+                    .iter()
+                    .map(|(comm, evals, bound)| {
+                        let bound = if comm.shifted.is_some() { *bound } else { None };
+                        (vec![evals[j]], bound)
+                    })
+                    .collect();
+                let v =
+                    combined_inner_product::<E::G1Affine>(&[*x], xi, &E::Fr::one(), &es, *length);
+
+                for (comm, xi_i) in terms.iter() {
+                    points.push(*comm);
+                    scalars.push(rand_base_i * xi_i);
+                }
+                scalars[0] -= &(rand_base_i * &v);
+                points.push(*w);
+                scalars.push(rand_base_i * x);
+
+                quotients.push(*w);
+                quotient_scalars.push(rand_base_i.into_repr());
+
+                rand_base_i *= &rand_base;
+            }
+        }
+
+        let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+        let lhs = VariableBaseMSM::multi_scalar_mul(&points, &scalars);
+        let rhs = VariableBaseMSM::multi_scalar_mul(&quotients, &quotient_scalars);
+        E::pairing(lhs.into_affine(), self.h) == E::pairing(rhs.into_affine(), self.h_tau)
+    }
+}
+
+// This function divides a polynomial by X - x, the remainder being its evaluation at x
+//     p: polynomial to divide
+//     x: root of the divisor
+//     RETURN: coefficients of the quotient
+fn quotient<F: Field>(p: &DensePolynomial<F>, x: F) -> Vec<F> {
+    let n = p.coeffs.len();
+    if n < 2 {
+        return vec![];
+    }
+    let mut q = vec![F::zero(); n - 1];
+    q[n - 2] = p.coeffs[n - 1];
+    for i in (0..n - 2).rev() {
+        q[i] = p.coeffs[i + 1] + &(x * &q[i + 1]);
+    }
+    q
+}
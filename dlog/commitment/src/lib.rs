@@ -2,7 +2,16 @@ mod combine;
 mod qnr_field;
 pub use qnr_field::*;
 pub mod commitment;
+pub mod fixed_base;
+pub mod glv;
+pub mod kzg;
+pub mod mapped_srs;
+pub mod scheme;
+mod segments;
+pub mod serialization;
+pub mod simulator;
 pub mod srs;
+pub mod validation;
 
 use mina_curves::pasta;
 
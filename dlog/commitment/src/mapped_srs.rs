@@ -0,0 +1,220 @@
+/*****************************************************************************************************************
+
+This source file implements the memory-mapped storage of SRS
+
+The generators are stored in a fixed-width affine encoding, the coordinates x and y of each of
+them being written in the fixed-width encoding of the base field, so that the generator of any
+index is found at a known offset of the file. The file is memory-mapped and only the prefix of
+the generators a circuit needs is ever read and deserialized.
+
+The layout of the file is
+
+1. the magic tag and the format version
+2. the width of a coordinate, the number of generators and the number of generators per block
+3. the blinding generator h
+4. the Blake2b digest of every block of generators
+5. the Blake2b digest of all the above
+6. the generators
+
+Opening the file checks the header against its digest, reading a prefix of the generators
+checks the blocks it spans against their digests and the generators to be valid points.
+
+*****************************************************************************************************************/
+
+use crate::commitment::CommitmentCurve;
+use crate::serialization::{read_header, read_len, write_header, write_len};
+use crate::srs::SRS;
+use crate::validation::{check_nonzero_point, check_nonzero_points, invalid_data};
+use crate::CommitmentField;
+use algebra::{FromBytes, PrimeField, ToBytes, Zero};
+use blake2::{Blake2b, Digest};
+use memmap::Mmap;
+use rayon::prelude::*;
+use std::cmp::min;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Result as IoResult, Write};
+use std::path::Path;
+
+const MAPPED_MAGIC: &[u8; 4] = b"SRSM";
+const BLOCK: usize = 1 << 12;
+const DIGEST: usize = 64;
+
+pub struct MappedSRS<G: CommitmentCurve> {
+    map: Mmap,
+    width: usize,   // width of a coordinate
+    depth: usize,   // number of generators g
+    block: usize,   // number of generators per block
+    offset: usize,  // offset of the first generator
+    digests: usize, // offset of the digests of the blocks
+    pub h: G,       // blinding
+}
+
+impl<G: CommitmentCurve> MappedSRS<G>
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: CommitmentField,
+{
+    // This function writes SRS in the fixed-width encoding, its cached commitments to Lagrange
+    // bases and fixed-base tables being left out
+    //     srs: SRS to write
+    //     writer: destination of the encoding
+    pub fn write<W: Write>(srs: &SRS<G>, mut writer: W) -> IoResult<()> {
+        let width = coordinate_width::<G>();
+        let points = srs
+            .g
+            .par_iter()
+            .map(|g| encode(g, width))
+            .collect::<IoResult<Vec<_>>>()?;
+
+        let mut header = vec![];
+        write_header(MAPPED_MAGIC, &mut header)?;
+        write_len(width, &mut header)?;
+        write_len(srs.g.len(), &mut header)?;
+        write_len(BLOCK, &mut header)?;
+        header.extend(encode(&srs.h, width)?);
+        for block in points.chunks(BLOCK) {
+            header.extend(digest(block.iter().map(|p| &p[..])));
+        }
+        let header_digest = digest(std::iter::once(&header[..]));
+
+        writer.write_all(&header)?;
+        writer.write_all(&header_digest)?;
+        for p in points.iter() {
+            writer.write_all(p)?;
+        }
+        Ok(())
+    }
+
+    // This function memory-maps an SRS file, checking its header only
+    //     path: path of the file written by write
+    pub fn open<P: AsRef<Path>>(path: P) -> IoResult<Self> {
+        let file = File::open(path)?;
+        // the map is read-only, the file being expected not to be modified while mapped
+        let map = unsafe { Mmap::map(&file)? };
+
+        let mut reader = &map[..];
+        read_header(MAPPED_MAGIC, &mut reader)?;
+        let width = read_len(&mut reader)?;
+        let depth = read_len(&mut reader)?;
+        let block = read_len(&mut reader)?;
+        if width != coordinate_width::<G>() || block == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Invalid SRS file header",
+            ));
+        }
+
+        // the header is not trusted before its digest is checked, nor authenticated by it
+        let digests = map.len() - reader.len() + 2 * width;
+        let offset = depth
+            .checked_add(block - 1)
+            .map(|n| n / block)
+            .and_then(|blocks| blocks.checked_add(1))
+            .and_then(|n| n.checked_mul(DIGEST))
+            .and_then(|n| n.checked_add(digests))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Invalid SRS file header"))?;
+        if depth
+            .checked_mul(2 * width)
+            .and_then(|n| n.checked_add(offset))
+            != Some(map.len())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Invalid SRS file length",
+            ));
+        }
+        if digest(std::iter::once(&map[0..offset - DIGEST])) != &map[offset - DIGEST..offset] {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Invalid SRS file checksum",
+            ));
+        }
+
+        let h = decode(&map[digests - 2 * width..digests], width)?;
+        check_nonzero_point(&h, "SRS generator h").map_err(invalid_data)?;
+        Ok(MappedSRS {
+            map,
+            width,
+            depth,
+            block,
+            offset,
+            digests,
+            h,
+        })
+    }
+
+    // This function returns the number of generators of the file
+    pub fn max_degree(&self) -> usize {
+        self.depth
+    }
+
+    // This function reads a prefix of the generators, touching only the blocks of the file
+    // it spans
+    //     depth: number of generators to read
+    //     RETURN: SRS of the given depth
+    pub fn prefix(&self, depth: usize) -> IoResult<SRS<G>> {
+        if depth > self.depth {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "SRS file shorter than the requested depth",
+            ));
+        }
+        let size = 2 * self.width;
+        let blocks = (depth + self.block - 1) / self.block;
+
+        let g = (0..blocks)
+            .into_par_iter()
+            .map(|i| {
+                let start = i * self.block;
+                let end = min(start + self.block, self.depth);
+                let bytes = &self.map[self.offset + start * size..self.offset + end * size];
+                let expected =
+                    &self.map[self.digests + i * DIGEST..self.digests + (i + 1) * DIGEST];
+                if digest(bytes.chunks(size)) != expected {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "Invalid SRS file checksum",
+                    ));
+                }
+                bytes[..(min(end, depth) - start) * size]
+                    .chunks(size)
+                    .map(|p| decode::<G>(p, self.width))
+                    .collect::<IoResult<Vec<_>>>()
+            })
+            .collect::<IoResult<Vec<_>>>()?
+            .concat();
+        check_nonzero_points(&g, "SRS generator g").map_err(invalid_data)?;
+        Ok(SRS::new(g, self.h))
+    }
+}
+
+// This function returns the width of the encoding of a coordinate
+fn coordinate_width<G: CommitmentCurve>() -> usize {
+    let mut bytes = vec![];
+    G::BaseField::zero().write(&mut bytes).unwrap();
+    bytes.len()
+}
+
+fn encode<G: CommitmentCurve>(p: &G, width: usize) -> IoResult<Vec<u8>> {
+    let (x, y) = p
+        .to_coordinates()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "SRS generator at infinity"))?;
+    let mut bytes = Vec::with_capacity(2 * width);
+    x.write(&mut bytes)?;
+    y.write(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn decode<G: CommitmentCurve>(bytes: &[u8], width: usize) -> IoResult<G> {
+    let x = G::BaseField::read(&bytes[0..width])?;
+    let y = G::BaseField::read(&bytes[width..2 * width])?;
+    Ok(G::of_coordinates(x, y))
+}
+
+fn digest<'a, I: Iterator<Item = &'a [u8]>>(chunks: I) -> Vec<u8> {
+    let mut h = Blake2b::new();
+    for chunk in chunks {
+        h.input(chunk);
+    }
+    h.result().to_vec()
+}
@@ -0,0 +1,283 @@
+/*****************************************************************************************************************
+
+This source file defines the interface of the polynomial commitment schemes
+
+A scheme commits to polynomials segment by segment, the last segment of a degree bounded
+polynomial being committed as well shifted to the end of the segment size, opens batches of
+commitments at vectors of evaluation points and verifies batches of such openings.
+
+The implementations are
+
+1. the inner product argument over the Marlin SRS, which needs no trusted setup and whose
+    opening proofs can be accumulated for recursion
+2. the KZG scheme over a pairing curve, in kzg.rs, with constant size opening proofs verified
+    with two pairings
+
+The plonk provers and verifiers are generic over the trait. The openings of both schemes are
+bound to the Fq sponge of the transcript and the IPA batches are verified against SRS prefixes,
+so both appear in the trait; the KZG scheme ignores the sponge and the group map and verifies
+prefixes of its SRS as it does the SRS itself. Verifying the failures of a batch one by one and
+accumulating the challenge polynomials for recursion stay specific to the inner product argument.
+
+*****************************************************************************************************************/
+
+use crate::commitment::{CommitmentCurve, OpeningProof, PolyComm};
+use crate::srs::SRS;
+use crate::validation::InvalidPoint;
+pub use crate::CommitmentField;
+use algebra::{AffineCurve, Zero};
+use ff_fft::DensePolynomial;
+use oracle::FqSponge;
+use rand_core::RngCore;
+
+type Fr<G> = <G as AffineCurve>::ScalarField;
+type Fq<G> = <G as AffineCurve>::BaseField;
+
+// batch of batched polynomial commitment opening proofs
+//     random oracle state
+//     vector of evaluation points
+//     polynomial scaling factor for this batched openinig proof
+//     eval scaling factor for this batched openinig proof
+//     batch/vector of polycommitments (opened in this batch), evaluation vectors and, optionally, max degrees
+//     opening proof for this batched opening
+pub type Batch<'a, G, EFqSponge, P> = Vec<(
+    EFqSponge,
+    Vec<Fr<G>>,
+    Fr<G>,
+    Fr<G>,
+    Vec<(&'a PolyComm<G>, Vec<&'a Vec<Fr<G>>>, Option<usize>)>,
+    &'a P,
+)>;
+
+pub trait PolynomialCommitment {
+    // group of the commitments
+    type Curve: CommitmentCurve;
+    type Proof: Clone;
+
+    // segment size of the commitments
+    fn segment_size(&self) -> usize;
+
+    // This function checks the commitment key to be a prefix of another, so that the openings
+    // made against it are verified against the other
+    //     other: commitment key of greater or equal segment size
+    //     RETURN: whether this key is a prefix of the other
+    fn is_prefix_of(&self, other: &Self) -> bool;
+
+    // This function commits a polynomial
+    //     plnm: polynomial to commit to
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     RETURN: tuple of: commitment, commitment randomness with the shape of the commitment
+    fn commit(
+        &self,
+        plnm: &DensePolynomial<Fr<Self::Curve>>,
+        max: Option<usize>,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<Self::Curve>, PolyComm<Fr<Self::Curve>>);
+
+    // This function commits a polynomial without blinding
+    //     plnm: polynomial to commit to
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     RETURN: commitment
+    fn commit_non_hiding(
+        &self,
+        plnm: &DensePolynomial<Fr<Self::Curve>>,
+        max: Option<usize>,
+    ) -> PolyComm<Self::Curve>;
+
+    // This function commits a polynomial with the shape of the hiding commitment, blinding it
+    // only if hiding
+    //     plnm: polynomial to commit to
+    //     max: maximal degree of the polynomial, if none, no degree bound
+    //     hiding: whether to blind the commitment
+    //     RETURN: tuple of: commitment, commitment randomness, zero if not hiding
+    fn commit_maybe_hiding(
+        &self,
+        plnm: &DensePolynomial<Fr<Self::Curve>>,
+        max: Option<usize>,
+        hiding: bool,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<Self::Curve>, PolyComm<Fr<Self::Curve>>) {
+        if hiding {
+            return self.commit(plnm, max, rng);
+        }
+        let mut c = self.commit_non_hiding(plnm, max);
+        if c.unshifted.is_empty() {
+            c.unshifted.push(Self::Curve::zero());
+        }
+        let w = c.map(|_| Fr::<Self::Curve>::zero());
+        (c, w)
+    }
+
+    // This function opens polynomial commitments in batch
+    //     group_map: map to the curve of the scheme, if it needs one
+    //     plnms: batch of polynomials to open commitments for with, optionally, max degrees
+    //         and commitment randomness
+    //     elm: evaluation point vector to open the commitments at
+    //     polyscale: polynomial scaling factor for opening commitments in batch
+    //     evalscale: eval scaling factor for opening commitments in batch
+    //     sponge: random oracle state
+    //     hiding: whether to blind the opening, for the provers which need no zero-knowledge
+    //     RETURN: commitment opening proof
+    fn open_maybe_hiding<
+        EFqSponge: Clone + FqSponge<Fq<Self::Curve>, Self::Curve, Fr<Self::Curve>>,
+    >(
+        &self,
+        group_map: &<Self::Curve as CommitmentCurve>::Map,
+        plnms: Vec<(
+            &DensePolynomial<Fr<Self::Curve>>,
+            Option<usize>,
+            PolyComm<Fr<Self::Curve>>,
+        )>,
+        elm: &Vec<Fr<Self::Curve>>,
+        polyscale: Fr<Self::Curve>,
+        evalscale: Fr<Self::Curve>,
+        sponge: EFqSponge,
+        hiding: bool,
+        rng: &mut dyn RngCore,
+    ) -> Self::Proof;
+
+    // This function opens polynomial commitments in batch, as open_maybe_hiding does when hiding
+    fn open<EFqSponge: Clone + FqSponge<Fq<Self::Curve>, Self::Curve, Fr<Self::Curve>>>(
+        &self,
+        group_map: &<Self::Curve as CommitmentCurve>::Map,
+        plnms: Vec<(
+            &DensePolynomial<Fr<Self::Curve>>,
+            Option<usize>,
+            PolyComm<Fr<Self::Curve>>,
+        )>,
+        elm: &Vec<Fr<Self::Curve>>,
+        polyscale: Fr<Self::Curve>,
+        evalscale: Fr<Self::Curve>,
+        sponge: EFqSponge,
+        rng: &mut dyn RngCore,
+    ) -> Self::Proof {
+        self.open_maybe_hiding(
+            group_map, plnms, elm, polyscale, evalscale, sponge, true, rng,
+        )
+    }
+
+    // This function checks the points of an untrusted opening proof to be valid
+    fn validate_proof(&self, proof: &Self::Proof) -> Result<(), InvalidPoint>;
+
+    // This function verifies batch of batched polynomial commitment opening proofs
+    // made against commitment keys which are prefixes of this one
+    //     group_map: map to the curve of the scheme, if it needs one
+    //     batch: batch of batched polynomial commitment opening proofs
+    //     lengths: segment sizes of the keys the proofs of the batch were made against
+    //     randomness source context
+    //     RETURN: verification status
+    fn verify_prefixes<EFqSponge: FqSponge<Fq<Self::Curve>, Self::Curve, Fr<Self::Curve>>>(
+        &self,
+        group_map: &<Self::Curve as CommitmentCurve>::Map,
+        batch: &mut Batch<Self::Curve, EFqSponge, Self::Proof>,
+        lengths: &[usize],
+        rng: &mut dyn RngCore,
+    ) -> bool;
+
+    // This function verifies batch of batched polynomial commitment opening proofs
+    // made against this commitment key
+    //     RETURN: verification status
+    fn batch_verify<EFqSponge: FqSponge<Fq<Self::Curve>, Self::Curve, Fr<Self::Curve>>>(
+        &self,
+        group_map: &<Self::Curve as CommitmentCurve>::Map,
+        batch: &mut Batch<Self::Curve, EFqSponge, Self::Proof>,
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        let lengths = vec![self.segment_size(); batch.len()];
+        self.verify_prefixes(group_map, batch, &lengths, rng)
+    }
+
+    // This function verifies a batched polynomial commitment opening proof
+    //     RETURN: verification status
+    fn verify<EFqSponge: FqSponge<Fq<Self::Curve>, Self::Curve, Fr<Self::Curve>>>(
+        &self,
+        group_map: &<Self::Curve as CommitmentCurve>::Map,
+        sponge: EFqSponge,
+        elm: Vec<Fr<Self::Curve>>,
+        polyscale: Fr<Self::Curve>,
+        evalscale: Fr<Self::Curve>,
+        polys: Vec<(
+            &PolyComm<Self::Curve>,
+            Vec<&Vec<Fr<Self::Curve>>>,
+            Option<usize>,
+        )>,
+        proof: &Self::Proof,
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        self.batch_verify(
+            group_map,
+            &mut vec![(sponge, elm, polyscale, evalscale, polys, proof)],
+            rng,
+        )
+    }
+}
+
+// The inner product argument over the Marlin SRS, binding the openings to the sponge
+impl<G: CommitmentCurve> PolynomialCommitment for SRS<G>
+where
+    G::ScalarField: CommitmentField,
+{
+    type Curve = G;
+    type Proof = OpeningProof<G>;
+
+    fn segment_size(&self) -> usize {
+        self.g().len()
+    }
+
+    fn is_prefix_of(&self, other: &Self) -> bool {
+        SRS::is_prefix_of(self, other)
+    }
+
+    fn commit(
+        &self,
+        plnm: &DensePolynomial<Fr<G>>,
+        max: Option<usize>,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<G>, PolyComm<Fr<G>>) {
+        SRS::commit(self, plnm, max, rng)
+    }
+
+    fn commit_non_hiding(&self, plnm: &DensePolynomial<Fr<G>>, max: Option<usize>) -> PolyComm<G> {
+        SRS::commit_non_hiding(self, plnm, max)
+    }
+
+    fn commit_maybe_hiding(
+        &self,
+        plnm: &DensePolynomial<Fr<G>>,
+        max: Option<usize>,
+        hiding: bool,
+        rng: &mut dyn RngCore,
+    ) -> (PolyComm<G>, PolyComm<Fr<G>>) {
+        SRS::commit_maybe_hiding(self, plnm, max, hiding, rng)
+    }
+
+    fn open_maybe_hiding<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        plnms: Vec<(&DensePolynomial<Fr<G>>, Option<usize>, PolyComm<Fr<G>>)>,
+        elm: &Vec<Fr<G>>,
+        polyscale: Fr<G>,
+        evalscale: Fr<G>,
+        sponge: EFqSponge,
+        hiding: bool,
+        rng: &mut dyn RngCore,
+    ) -> OpeningProof<G> {
+        SRS::open_maybe_hiding(
+            self, group_map, plnms, elm, polyscale, evalscale, sponge, hiding, rng,
+        )
+    }
+
+    fn validate_proof(&self, proof: &OpeningProof<G>) -> Result<(), InvalidPoint> {
+        proof.validate()
+    }
+
+    fn verify_prefixes<EFqSponge: FqSponge<Fq<G>, G, Fr<G>>>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut Batch<G, EFqSponge, OpeningProof<G>>,
+        lengths: &[usize],
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        SRS::verify_prefixes(self, group_map, batch, lengths, rng)
+    }
+}
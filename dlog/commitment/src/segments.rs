@@ -0,0 +1,143 @@
+/*****************************************************************************************************************
+
+This source file implements the segmented commitment of polynomials against a vector of bases
+
+A polynomial longer than the bases is committed segment by segment, each segment of the
+coefficients being committed against the bases from the first one on. The last segment of a
+degree bounded polynomial is committed as well shifted to the right edge of the bases.
+
+The segments of a polynomial held in memory are committed in parallel. The coefficients of
+a polynomial may as well be streamed from an iterator or a reader, then the segments are read
+and committed in batches of a segment per thread, so that the polynomial is never held in memory.
+
+*****************************************************************************************************************/
+
+use crate::commitment::PolyComm;
+use algebra::{AffineCurve, ProjectiveCurve, Zero};
+use ff_fft::DensePolynomial;
+use rayon::prelude::*;
+use std::cmp::min;
+use std::io::Result as IoResult;
+
+type Fr<G> = <G as AffineCurve>::ScalarField;
+
+// This function commits a polynomial segment by segment against bases
+//     n: number of bases
+//     plnm: polynomial to commit to
+//     max: maximal degree of the polynomial, if none, no degree bound
+//     msm: multi-scalar multiplication of the bases from an offset on
+//     RETURN: unbounded commitment vector, optional bounded commitment
+pub(crate) fn commit_segments<G: AffineCurve>(
+    n: usize,
+    plnm: &DensePolynomial<Fr<G>>,
+    max: Option<usize>,
+    msm: &(dyn Fn(usize, &[Fr<G>]) -> G::Projective + Sync),
+) -> PolyComm<G> {
+    let p = plnm.coeffs.len();
+
+    // committing all the segments without shifting
+    let unshifted = if plnm.is_zero() {
+        Vec::new()
+    } else {
+        let segments: Vec<_> = plnm.coeffs.par_chunks(n).map(|s| msm(0, s)).collect();
+        normalize::<G>(segments)
+    };
+
+    // committing only last segment shifted to the right edge of SRS
+    let shifted = match max {
+        None => None,
+        Some(max) => {
+            let start = max - (max % n);
+            if plnm.is_zero() || start >= p {
+                Some(G::zero())
+            } else if max % n == 0 {
+                None
+            } else {
+                let segment = &plnm.coeffs[start..min(start + n, p)];
+                Some(msm(n - (max % n), segment).into_affine())
+            }
+        }
+    };
+
+    PolyComm::<G> { unshifted, shifted }
+}
+
+// This function commits a polynomial segment by segment against bases, reading its
+// coefficients from a source in batches of a segment per thread
+//     n: number of bases
+//     coeffs: source of the coefficients of the polynomial, lowest degree first
+//     max: maximal degree of the polynomial, if none, no degree bound
+//     msm: multi-scalar multiplication of the bases from an offset on
+//     RETURN: unbounded commitment vector, optional bounded commitment, as commit_segments
+pub(crate) fn commit_segments_stream<G: AffineCurve, I: Iterator<Item = IoResult<Fr<G>>>>(
+    n: usize,
+    mut coeffs: I,
+    max: Option<usize>,
+    msm: &(dyn Fn(usize, &[Fr<G>]) -> G::Projective + Sync),
+) -> IoResult<PolyComm<G>> {
+    let threads = rayon::current_num_threads();
+    // the index of the segment holding the degree bound, shifted if the bound is inside it
+    let bounded = max.map(|max| (max / n, max % n));
+
+    let mut p = 0;
+    let mut nonzero = false;
+    let mut unshifted = vec![];
+    let mut shifted = None;
+    loop {
+        let mut segments = vec![];
+        while segments.len() < threads {
+            let segment: Vec<Fr<G>> = coeffs.by_ref().take(n).collect::<IoResult<_>>()?;
+            let last = segment.len() < n;
+            if !segment.is_empty() {
+                segments.push(segment);
+            }
+            if last {
+                break;
+            }
+        }
+        if segments.is_empty() {
+            break;
+        }
+
+        let first = unshifted.len();
+        p += segments.iter().map(|s| s.len()).sum::<usize>();
+        nonzero |= segments.iter().flatten().any(|c| !c.is_zero());
+        unshifted.par_extend(segments.par_iter().map(|s| msm(0, s)));
+        if let Some((j, r)) = bounded {
+            if r != 0 && j >= first && j < unshifted.len() {
+                shifted = Some(msm(n - r, &segments[j - first]));
+            }
+        }
+        if segments.last().map_or(true, |s| s.len() < n) {
+            break;
+        }
+    }
+
+    // the zero polynomial, and the polynomials without coefficients past the degree bound,
+    // are committed as by commit_segments
+    let shifted = match max {
+        None => None,
+        Some(max) => {
+            if !nonzero || max - (max % n) >= p {
+                Some(G::zero())
+            } else if max % n == 0 {
+                None
+            } else {
+                shifted.map(|s: G::Projective| s.into_affine())
+            }
+        }
+    };
+    Ok(PolyComm::<G> {
+        unshifted: if nonzero {
+            normalize::<G>(unshifted)
+        } else {
+            Vec::new()
+        },
+        shifted,
+    })
+}
+
+fn normalize<G: AffineCurve>(mut v: Vec<G::Projective>) -> Vec<G> {
+    G::Projective::batch_normalization(v.as_mut_slice());
+    v.iter().map(|p| p.into_affine()).collect()
+}
@@ -0,0 +1,63 @@
+/*****************************************************************************************************************
+
+This source file implements the building blocks of the canonical binary encoding of
+commitments and proofs.
+
+Curve points are written compressed, vectors are prefixed with their length as u64
+and top-level objects start with a 4-byte magic tag followed by the format version.
+
+*****************************************************************************************************************/
+
+use algebra::{AffineCurve, CanonicalDeserialize, CanonicalSerialize, FromBytes, ToBytes};
+pub use oracle::serialization::{read_fields, read_len, write_fields, write_len, MAX_PREALLOC};
+use std::cmp::min;
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
+
+pub const FORMAT_VERSION: u8 = 1;
+
+pub fn write_header<W: Write>(magic: &[u8; 4], mut writer: W) -> IoResult<()> {
+    writer.write_all(magic)?;
+    FORMAT_VERSION.write(&mut writer)
+}
+
+pub fn read_header<R: Read>(magic: &[u8; 4], mut reader: R) -> IoResult<()> {
+    let mut tag = [0u8; 4];
+    reader.read_exact(&mut tag)?;
+    if &tag != magic {
+        return Err(Error::new(ErrorKind::InvalidData, "Invalid magic header"));
+    }
+    let version = u8::read(&mut reader)?;
+    if version != FORMAT_VERSION {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Unsupported format version {}", version),
+        ));
+    }
+    Ok(())
+}
+
+pub fn write_point<G: AffineCurve, W: Write>(g: &G, mut writer: W) -> IoResult<()> {
+    g.serialize(&mut writer)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{:?}", e)))
+}
+
+pub fn read_point<G: AffineCurve, R: Read>(mut reader: R) -> IoResult<G> {
+    G::deserialize(&mut reader).map_err(|e| Error::new(ErrorKind::InvalidData, format!("{:?}", e)))
+}
+
+pub fn write_points<G: AffineCurve, W: Write>(xs: &[G], mut writer: W) -> IoResult<()> {
+    write_len(xs.len(), &mut writer)?;
+    for x in xs.iter() {
+        write_point(x, &mut writer)?;
+    }
+    Ok(())
+}
+
+pub fn read_points<G: AffineCurve, R: Read>(mut reader: R) -> IoResult<Vec<G>> {
+    let n = read_len(&mut reader)?;
+    let mut xs = Vec::with_capacity(min(n, MAX_PREALLOC));
+    for _ in 0..n {
+        xs.push(read_point(&mut reader)?);
+    }
+    Ok(xs)
+}
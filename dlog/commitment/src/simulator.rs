@@ -0,0 +1,200 @@
+/*****************************************************************************************************************
+
+This source file implements the simulator of the opening proofs of the inner product argument
+
+The opening proof is zero-knowledge in the random oracle model: a simulator, which programs the
+outputs of the random oracle and knows neither the polynomials nor the commitment randomness,
+produces opening proofs distributed as those of SRS::open for the same commitments, evaluations
+and challenges. With the challenges fixed,
+
+1. L_i and R_i are uniform, being blinded by independent multiples of H
+2. z1 and z2 are uniform, being blinded by the nonces d and r_delta
+3. sg is the commitment to the polynomial b of the challenges
+4. delta is then the only point satisfying the verification equation
+    c Q + delta = z1 (sg + b0 U) + z2 H
+
+The opening proofs of the prover and of the simulator are compared with the sponge programmed
+with the same challenges, which tests that the opening proofs do not leak their witness.
+
+*****************************************************************************************************************/
+
+use crate::commitment::{
+    b_poly, b_poly_coefficients, combined_commitment_terms, combined_inner_product, to_group,
+    CommitmentCurve, OpeningProof, PolyComm,
+};
+use crate::srs::SRS;
+use crate::CommitmentField;
+use algebra::{AffineCurve, Field, One, ProjectiveCurve, UniformRand, Zero};
+use oracle::{poseidon::ArithmeticSpongeParams, sponge::ScalarChallenge, FqSponge};
+use rand_core::RngCore;
+use std::collections::VecDeque;
+
+type Fr<G> = <G as AffineCurve>::ScalarField;
+type Fq<G> = <G as AffineCurve>::BaseField;
+
+// A random oracle whose outputs are programmed, ignoring what it absorbs
+#[derive(Clone)]
+pub struct ProgrammedSponge<Fq, Fr> {
+    fq: VecDeque<Fq>,
+    fr: VecDeque<Fr>,
+}
+
+impl<Fq, Fr> ProgrammedSponge<Fq, Fr> {
+    // This function programs the outputs of the random oracle
+    //     fq: base field challenges, in the order they are squeezed
+    //     fr: scalar field challenges, in the order they are squeezed
+    pub fn program(fq: Vec<Fq>, fr: Vec<Fr>) -> Self {
+        ProgrammedSponge {
+            fq: fq.into(),
+            fr: fr.into(),
+        }
+    }
+}
+
+impl<Fq: Field, G, Fr> FqSponge<Fq, G, Fr> for ProgrammedSponge<Fq, Fr> {
+    // The random oracle with no programmed output, which has to be programmed before squeezing
+    fn new(_params: ArithmeticSpongeParams<Fq>) -> Self {
+        ProgrammedSponge::program(vec![], vec![])
+    }
+
+    fn absorb_g(&mut self, _g: &[G]) {}
+
+    fn absorb_fr(&mut self, _x: &[Fr]) {}
+
+    fn challenge(&mut self) -> Fr {
+        self.fr
+            .pop_front()
+            .expect("programmed sponge has no scalar challenge left")
+    }
+
+    fn challenge_fq(&mut self) -> Fq {
+        self.fq
+            .pop_front()
+            .expect("programmed sponge has no base field challenge left")
+    }
+
+    fn digest(mut self) -> Fr {
+        FqSponge::<Fq, G, Fr>::challenge(&mut self)
+    }
+}
+
+// The challenges of an opening proof, as squeezed from the sponge
+#[derive(Clone, Debug)]
+pub struct OpeningChallenges<G: AffineCurve> {
+    pub u: Fq<G>,                            // base field challenge mapped to the point U
+    pub rounds: Vec<ScalarChallenge<Fr<G>>>, // challenges of the rounds
+    pub c: ScalarChallenge<Fr<G>>,           // challenge of the final Schnorr proof
+}
+
+impl<G: AffineCurve> OpeningChallenges<G> {
+    // This function samples uniform challenges
+    //     rounds: number of rounds of the opening proof
+    pub fn rand(rounds: usize, rng: &mut dyn RngCore) -> Self {
+        OpeningChallenges {
+            u: Fq::<G>::rand(rng),
+            rounds: (0..rounds)
+                .map(|_| ScalarChallenge(Fr::<G>::rand(rng)))
+                .collect(),
+            c: ScalarChallenge(Fr::<G>::rand(rng)),
+        }
+    }
+
+    // This function programs a sponge to squeeze the challenges, in the order of the opening
+    pub fn sponge(&self) -> ProgrammedSponge<Fq<G>, Fr<G>> {
+        let mut fr: Vec<_> = self.rounds.iter().map(|ScalarChallenge(c)| *c).collect();
+        let ScalarChallenge(c) = self.c;
+        fr.push(c);
+        ProgrammedSponge::program(vec![self.u], fr)
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G>
+where
+    G::ScalarField: CommitmentField,
+{
+    // This function simulates the opening proof of a batch of polynomial commitments, without
+    // the polynomials and the commitment randomness
+    //     group_map: map of the base field to the curve
+    //     elm: vector of evaluation points
+    //     polyscale: polynomial scaling factor for opening commitments in batch
+    //     evalscale: eval scaling factor for opening commitments in batch
+    //     polys: batch of commitments, evaluation vectors and, optionally, max degrees
+    //     challenges: challenges of the random oracle, which the simulator programs
+    //     RETURN: opening proof verifying against the sponge programmed with the challenges
+    pub fn simulate_opening(
+        &self,
+        group_map: &G::Map,
+        elm: &Vec<Fr<G>>,
+        polyscale: Fr<G>,
+        evalscale: Fr<G>,
+        polys: &Vec<(&PolyComm<G>, Vec<&Vec<Fr<G>>>, Option<usize>)>,
+        challenges: &OpeningChallenges<G>,
+        rng: &mut dyn RngCore,
+    ) -> OpeningProof<G> {
+        let es: Vec<_> = polys
+            .iter()
+            .map(|(comm, evals, bound)| {
+                let bound = if comm.shifted.is_some() { *bound } else { None };
+                (evals.clone(), bound)
+            })
+            .collect();
+        let combined_inner_product =
+            combined_inner_product::<G>(elm, &polyscale, &evalscale, &es, self.g.len());
+        let u: G = to_group(group_map, challenges.u);
+
+        let chal: Vec<_> = challenges
+            .rounds
+            .iter()
+            .map(|c| c.to_field(&self.endo_r))
+            .collect();
+        let c = challenges.c.to_field(&self.endo_r);
+
+        // L and R are uniform points
+        let lr: Vec<(G, G)> = chal
+            .iter()
+            .map(|_| {
+                (
+                    self.h_mul(Fr::<G>::rand(rng)).into_affine(),
+                    self.h_mul(Fr::<G>::rand(rng)).into_affine(),
+                )
+            })
+            .collect();
+
+        // the combined commitment folded with L and R
+        //     Q = sum_i xi^i C_i + v U + sum_j (u_j^-1 L_j + u_j R_j)
+        let mut points = vec![u];
+        let mut scalars = vec![combined_inner_product];
+        for (comm, xi_i) in combined_commitment_terms(polys, polyscale) {
+            points.push(comm);
+            scalars.push(xi_i);
+        }
+        for ((l, r), u_j) in lr.iter().zip(chal.iter()) {
+            points.extend(vec![*l, *r]);
+            scalars.extend(vec![u_j.inverse().unwrap(), *u_j]);
+        }
+        let q = self.msm(&points, &scalars);
+
+        let sg = self.g_msm(0, &b_poly_coefficients(&chal)).into_affine();
+        let b0 = elm
+            .iter()
+            .fold((Fr::<G>::zero(), Fr::<G>::one()), |(acc, scale), e| {
+                (acc + &(scale * &b_poly(&chal, *e)), scale * &evalscale)
+            })
+            .0;
+
+        // z1 and z2 are uniform scalars, delta satisfies the verification equation
+        let z1 = Fr::<G>::rand(rng);
+        let z2 = Fr::<G>::rand(rng);
+        let delta = ((sg.into_projective() + &u.mul(b0)).into_affine().mul(z1) + &self.h_mul(z2)
+            - &q.into_affine().mul(c))
+            .into_affine();
+
+        OpeningProof {
+            lr,
+            delta,
+            z1,
+            z2,
+            sg,
+        }
+    }
+}
@@ -2,24 +2,99 @@
 
 This source file implements the Marlin structured reference string primitive
 
+Commitments to the Lagrange bases of evaluation domains can be computed, cached
+and serialized along with SRS
+
+Fixed-base tables of the generators can be precomputed and serialized along with SRS,
+the commitments and the opening proofs then use fixed-base multi-scalar multiplication
+
+The generators are derived in parallel by hashing their indices to the curve, so an SRS can
+be extended to a larger depth deriving only the missing generators, and an SRS read from a
+file can be checked by recomputing a random sample of its generators
+
+The digests of the prefixes of SRS are computed once when it is created or read, so that an
+SRS is checked to be a prefix of another without comparing all their generators. The
+generators are only read through accessors outside of this crate, so that the digests, the
+fixed-base tables and the precomputation of the endomorphism do not go stale
+
 *****************************************************************************************************************/
 
-use crate::commitment::CommitmentCurve;
+use crate::commitment::{CommitmentCurve, PolyComm};
+use crate::fixed_base::FixedBaseTable;
+use crate::serialization::{read_header, read_len, write_header, write_len};
+use crate::validation::{check_nonzero_point, check_nonzero_points, check_points, invalid_data};
 pub use crate::{CommitmentField, QnrField};
-use algebra::{BigInteger, FromBytes, PrimeField, ToBytes};
+use algebra::{
+    AffineCurve, BigInteger, Field, FromBytes, One, PrimeField, ProjectiveCurve, ToBytes, Zero,
+};
 use array_init::array_init;
 use blake2::{Blake2b, Digest};
+use ff_fft::{EvaluationDomain, Radix2EvaluationDomain as D};
 use groupmap::GroupMap;
-use std::io::{Read, Result as IoResult, Write};
+use rand_core::RngCore;
+use rayon::prelude::*;
+use std::cmp::min;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
+use std::ops::Range;
+
+const LAGRANGE_MAGIC: &[u8; 4] = b"LGRB";
 
 #[derive(Debug, Clone)]
 pub struct SRS<G: CommitmentCurve> {
-    pub g: Vec<G>, // for committing polynomials
-    pub h: G,      // blinding
+    pub(crate) g: Vec<G>, // for committing polynomials
+    pub(crate) h: G,      // blinding
+
+    // Commitments to the Lagrange bases of the domains, keyed by domain size
+    pub lagrange_bases: HashMap<usize, Vec<PolyComm<G>>>,
+
+    // Fixed-base tables of the generators g followed by h
+    pub fixed_base: Option<FixedBaseTable<G>>,
 
     // Coefficients for the curve endomorphism
     pub endo_r: G::ScalarField,
     pub endo_q: G::BaseField,
+
+    // Precomputation of the multi-scalar multiplication using the endomorphism
+    pub(crate) glv: G::Glv,
+
+    // Digests of the prefixes of SRS of every multiple of DIGEST_STRIDE length, computed once
+    // when SRS is created or read
+    pub(crate) prefix_digests: Vec<Vec<u8>>,
+}
+
+// Number of generators between the digests of the prefixes of SRS
+const DIGEST_STRIDE: usize = 1 << 6;
+
+// This function computes the digests of the prefixes of SRS of every multiple of DIGEST_STRIDE
+// length, chaining the digest of the blinding generator with those of the blocks of generators
+//     h: blinding generator
+//     g: generators
+//     RETURN: digests of the prefixes of length 0, DIGEST_STRIDE, 2 * DIGEST_STRIDE, ...
+fn prefix_digests<G: CommitmentCurve>(h: &G, g: &[G]) -> Vec<Vec<u8>> {
+    let encode = |points: &[G]| {
+        let mut hasher = Blake2b::new();
+        let mut bytes = vec![];
+        for p in points {
+            p.write(&mut bytes).unwrap();
+        }
+        hasher.input(&bytes);
+        hasher.result().to_vec()
+    };
+    let blocks: Vec<_> = g
+        .par_chunks(DIGEST_STRIDE)
+        .filter(|block| block.len() == DIGEST_STRIDE)
+        .map(|block| encode(block))
+        .collect();
+
+    let mut digests = vec![encode(&[*h])];
+    for block in blocks {
+        let mut hasher = Blake2b::new();
+        hasher.input(digests.last().unwrap());
+        hasher.input(&block);
+        digests.push(hasher.result().to_vec());
+    }
+    digests
 }
 
 pub fn endos<G: CommitmentCurve>() -> (G::BaseField, G::ScalarField)
@@ -60,6 +135,48 @@ where
     G::of_coordinates(x, y)
 }
 
+// This function derives the generator of SRS of the given index
+//     m: group map
+//     i: index of the generator
+fn generator<G: CommitmentCurve>(m: &G::Map, i: usize) -> G
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: CommitmentField,
+{
+    let mut h = Blake2b::new();
+    h.input(&(i as u32).to_be_bytes());
+    point_of_random_bytes(m, &h.result())
+}
+
+// This function derives in parallel the generators of SRS of a range of indices,
+// the group map being set up once per thread
+fn generators<G: CommitmentCurve>(indices: Range<usize>) -> Vec<G>
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: CommitmentField,
+{
+    indices
+        .into_par_iter()
+        .map_init(G::Map::setup, |m, i| generator::<G>(m, i))
+        .collect()
+}
+
+// This function derives the blinding generator of SRS
+fn blinding_generator<G: CommitmentCurve>(m: &G::Map) -> G
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: CommitmentField,
+{
+    const MISC: usize = 1;
+    let [h]: [G; MISC] = array_init(|i| {
+        let mut h = Blake2b::new();
+        h.input("srs_misc".as_bytes());
+        h.input(&(i as u32).to_be_bytes());
+        point_of_random_bytes(m, &h.result())
+    });
+    h
+}
+
 impl<G: CommitmentCurve> SRS<G>
 where
     G::BaseField: PrimeField,
@@ -69,38 +186,77 @@ where
         self.g.len()
     }
 
-    // This function creates SRS instance for circuits up to depth d
-    //      depth: maximal depth of SRS string
-    //      size: circuit size
-    pub fn create(depth: usize) -> Self {
-        let m = G::Map::setup();
-
-        let g: Vec<_> = (0..depth)
-            .map(|i| {
-                let mut h = Blake2b::new();
-                h.input(&(i as u32).to_be_bytes());
-                point_of_random_bytes(&m, &h.result())
-            })
-            .collect();
-
+    // This function creates SRS instance of the given generators, computing the digests of its
+    // prefixes and the precomputation of the endomorphism. The generators are not checked
+    //      g: generators for committing polynomials
+    //      h: blinding generator
+    pub fn new(g: Vec<G>, h: G) -> Self {
         let (endo_q, endo_r) = endos::<G>();
-
-        const MISC: usize = 1;
-        let [h]: [G; MISC] = array_init(|i| {
-            let mut h = Blake2b::new();
-            h.input("srs_misc".as_bytes());
-            h.input(&(i as u32).to_be_bytes());
-            point_of_random_bytes(&m, &h.result())
-        });
-
         SRS {
+            prefix_digests: prefix_digests(&h, &g),
             g,
             h,
+            lagrange_bases: HashMap::new(),
+            fixed_base: None,
             endo_r,
             endo_q,
+            glv: G::glv(endo_q, endo_r),
         }
     }
 
+    // This function creates SRS instance for circuits up to depth d,
+    // the generators being derived in parallel
+    //      depth: maximal depth of SRS string
+    //      size: circuit size
+    pub fn create(depth: usize) -> Self {
+        let g = generators::<G>(0..depth);
+        let h = blinding_generator::<G>(&G::Map::setup());
+        Self::new(g, h)
+    }
+
+    // This function extends SRS to a larger depth, deriving only the missing generators.
+    // The cached commitments to the Lagrange bases of the domains larger than the former depth
+    // are dropped, as their segments change, and the fixed-base tables are extended
+    //      depth: new maximal depth of SRS string
+    pub fn extend(&mut self, depth: usize) {
+        let n = self.g.len();
+        if depth <= n {
+            return;
+        }
+        let g = generators::<G>(n..depth);
+
+        self.lagrange_bases.retain(|size, _| *size <= n);
+        if let Some(table) = &mut self.fixed_base {
+            let h = table.multiples.pop().unwrap();
+            table
+                .multiples
+                .extend(FixedBaseTable::create(&g, table.window).multiples);
+            table.multiples.push(h);
+        }
+        self.g.extend(g);
+        self.prefix_digests = prefix_digests(&self.h, &self.g);
+    }
+
+    // This function checks that the generators of SRS are derived as create derives them,
+    // recomputing the blinding generator and a random sample of the generators
+    //      samples: number of generators to recompute
+    //      RETURN: whether the sampled generators match
+    pub fn verify_generators(&self, samples: usize, rng: &mut dyn RngCore) -> bool {
+        let m = G::Map::setup();
+        if self.h != blinding_generator::<G>(&m) {
+            return false;
+        }
+        if self.g.is_empty() {
+            return true;
+        }
+        let indices: Vec<_> = (0..samples)
+            .map(|_| (rng.next_u64() % self.g.len() as u64) as usize)
+            .collect();
+        indices
+            .into_par_iter()
+            .all(|i| self.g[i] == generator::<G>(&m, i))
+    }
+
     pub fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
         u64::write(&(self.g.len() as u64), &mut writer)?;
         for x in &self.g {
@@ -118,23 +274,267 @@ where
         }
 
         let h = G::read(&mut reader)?;
-        let (endo_q, endo_r) = endos::<G>();
-        Ok(SRS {
-            g,
-            h,
-            endo_r,
-            endo_q,
-        })
+        check_nonzero_points(&g, "SRS generator g").map_err(invalid_data)?;
+        check_nonzero_point(&h, "SRS generator h").map_err(invalid_data)?;
+        Ok(Self::new(g, h))
+    }
+
+    // This function computes and caches the commitments to the Lagrange basis of the domain.
+    // The commitments are split into segments of SRS length, as the commitments of the
+    // interpolated basis polynomials would be
+    //     domain: evaluation domain
+    pub fn add_lagrange_basis(&mut self, domain: D<G::ScalarField>) {
+        let n = domain.size();
+        if self.lagrange_bases.contains_key(&n) {
+            return;
+        }
+
+        // L_i = 1/n sum_j w^{-ij} X^j, so that the segments of the commitments to all the
+        // L_i are the inverse FFT of the generators of SRS placed at the segment coefficients
+        let segments = (0..(n + self.g.len() - 1) / self.g.len())
+            .map(|k| {
+                let start = k * self.g.len();
+                let mut v = vec![G::Projective::zero(); n];
+                for j in start..min(start + self.g.len(), n) {
+                    v[j] = self.g[j - start].into_projective();
+                }
+                group_ifft::<G>(&mut v, domain);
+                G::Projective::batch_normalization(v.as_mut_slice());
+                v.iter().map(|x| x.into_affine()).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let basis = (0..n)
+            .map(|i| PolyComm {
+                unshifted: segments.iter().map(|s| s[i]).collect(),
+                shifted: None,
+            })
+            .collect();
+        self.lagrange_bases.insert(n, basis);
+    }
+
+    // This function returns the cached commitments to the Lagrange basis of the domain
+    pub fn lagrange_basis(&self, domain: D<G::ScalarField>) -> Option<&Vec<PolyComm<G>>> {
+        self.lagrange_bases.get(&domain.size())
+    }
+
+    pub fn write_lagrange_bases<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        write_header(LAGRANGE_MAGIC, &mut writer)?;
+        write_len(self.g.len(), &mut writer)?;
+        let mut sizes: Vec<_> = self.lagrange_bases.keys().collect();
+        sizes.sort();
+        write_len(sizes.len(), &mut writer)?;
+        for n in sizes {
+            write_len(*n, &mut writer)?;
+            for comm in self.lagrange_bases[n].iter() {
+                comm.write(&mut writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    // This function reads the commitments to Lagrange bases into the cache,
+    // they have to be computed against an SRS of the same length
+    pub fn read_lagrange_bases<R: Read>(&mut self, mut reader: R) -> IoResult<()> {
+        read_header(LAGRANGE_MAGIC, &mut reader)?;
+        if read_len(&mut reader)? != self.g.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Lagrange bases computed for an SRS of different length",
+            ));
+        }
+        for _ in 0..read_len(&mut reader)? {
+            let n = read_len(&mut reader)?;
+            if !n.is_power_of_two() {
+                return Err(Error::new(ErrorKind::InvalidData, "Invalid domain size"));
+            }
+            let mut basis = vec![];
+            for _ in 0..n {
+                let comm = PolyComm::<G>::read(&mut reader)?;
+                comm.validate("SRS Lagrange basis commitment")
+                    .map_err(invalid_data)?;
+                basis.push(comm);
+            }
+            self.lagrange_bases.insert(n, basis);
+        }
+        Ok(())
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G> {
+    // This function returns the generators for committing polynomials
+    pub fn g(&self) -> &[G] {
+        &self.g
+    }
+
+    // This function returns the blinding generator
+    pub fn h(&self) -> G {
+        self.h
+    }
+
+    // This function checks SRS to be a prefix of another, comparing the digests of their
+    // prefixes and the fewer than DIGEST_STRIDE generators past the last digest instead of all
+    // the generators
+    //     other: SRS at least as long
+    //     RETURN: whether SRS is a prefix of other
+    pub fn is_prefix_of(&self, other: &SRS<G>) -> bool {
+        let n = self.g.len();
+        let k = n / DIGEST_STRIDE;
+        let digests = match (self.prefix_digests.get(k), other.prefix_digests.get(k)) {
+            (Some(digest), Some(other_digest)) => digest == other_digest,
+            _ => false,
+        };
+        n <= other.g.len()
+            && digests
+            && self.g[k * DIGEST_STRIDE..] == other.g[k * DIGEST_STRIDE..n]
+    }
+
+    // This function precomputes the fixed-base tables of the generators of SRS,
+    // which take ceil(MODULUS_BITS / window) points per generator
+    //     window: window size in bits
+    pub fn add_fixed_base_table(&mut self, window: usize) {
+        let mut bases = self.g.clone();
+        bases.push(self.h);
+        self.fixed_base = Some(FixedBaseTable::create(&bases, window));
+    }
+
+    // This function computes the multi-scalar multiplication over a range of the generators g,
+    // with the fixed-base tables if precomputed. As with VariableBaseMSM, the scalars past the
+    // end of SRS are ignored
+    //     offset: index of the generator the first scalar multiplies
+    //     scalars: scalars multiplying the generators from offset on
+    pub fn g_msm(&self, offset: usize, scalars: &[G::ScalarField]) -> G::Projective {
+        let scalars = &scalars[0..min(scalars.len(), self.g.len() - offset)];
+        match &self.fixed_base {
+            Some(table) => table.msm(
+                offset,
+                &scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>(),
+            ),
+            None => self.msm(&self.g[offset..], scalars),
+        }
+    }
+
+    // This function computes the multi-scalar multiplication of arbitrary bases, using the
+    // endomorphism of the curve
+    pub fn msm(&self, bases: &[G], scalars: &[G::ScalarField]) -> G::Projective {
+        G::msm(&self.glv, bases, scalars)
+    }
+
+    // This function multiplies the blinding generator h, with its fixed-base table if precomputed
+    pub fn h_mul(&self, scalar: G::ScalarField) -> G::Projective {
+        match &self.fixed_base {
+            Some(table) => table.msm(self.g.len(), &[scalar.into_repr()]),
+            None => self.h.mul(scalar),
+        }
+    }
+
+    pub fn write_fixed_base_table<W: Write>(&self, writer: W) -> IoResult<()> {
+        match &self.fixed_base {
+            Some(table) => table.write(writer),
+            None => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Fixed-base table was not added to SRS",
+            )),
+        }
+    }
+
+    // This function reads the fixed-base table of the generators,
+    // it has to be computed for an SRS of the same length. The first multiples are checked to
+    // be the generators, and the others to be consistent with them
+    //     rng: randomness source context
+    pub fn read_fixed_base_table<R: Read>(
+        &mut self,
+        reader: R,
+        rng: &mut dyn RngCore,
+    ) -> IoResult<()> {
+        let table = FixedBaseTable::<G>::read(reader)?;
+        if table.multiples.len() != self.g.len() + 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Fixed-base table computed for an SRS of different length",
+            ));
+        }
+        table
+            .multiples
+            .par_iter()
+            .try_for_each(|m| check_points(m, "SRS fixed-base table"))
+            .map_err(invalid_data)?;
+        // the first multiples are the generators themselves
+        if table
+            .multiples
+            .iter()
+            .map(|m| m[0])
+            .ne(self.g.iter().chain([self.h].iter()).copied())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Fixed-base table computed for different generators",
+            ));
+        }
+        if !table.check_multiples(rng) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Inconsistent multiples of the fixed-base table",
+            ));
+        }
+        self.fixed_base = Some(table);
+        Ok(())
+    }
+}
+
+// This function computes in place the inverse FFT over the domain of a vector of group elements,
+// following the iterative radix-2 algorithm ff-fft uses for field elements
+//     a: vector of group elements of the size of the domain
+//     domain: evaluation domain
+fn group_ifft<G: AffineCurve>(a: &mut [G::Projective], domain: D<G::ScalarField>) {
+    let n = a.len();
+    assert_eq!(n, domain.size());
+    let log_n = domain.log_size_of_group;
+
+    for k in 0..n {
+        let rk = bitreverse(k as u32, log_n) as usize;
+        if k < rk {
+            a.swap(k, rk);
+        }
+    }
+
+    let mut m = 1;
+    while m < n {
+        // inverse of the primitive 2m-th root of unity
+        let w_m = domain.group_gen_inv.pow(&[(n / (2 * m)) as u64]);
+        a.par_chunks_mut(2 * m).for_each(|chunk| {
+            let mut w = G::ScalarField::one();
+            for j in 0..m {
+                let t = chunk[j + m].mul(w.into_repr());
+                chunk[j + m] = chunk[j] - &t;
+                chunk[j] += &t;
+                w *= &w_m;
+            }
+        });
+        m *= 2;
+    }
+
+    let size_inv = domain.size_inv.into_repr();
+    a.par_iter_mut().for_each(|x| *x = x.mul(size_inv));
+}
+
+fn bitreverse(mut n: u32, l: u32) -> u32 {
+    let mut r = 0;
+    for _ in 0..l {
+        r = (r << 1) | (n & 1);
+        n >>= 1;
     }
+    r
 }
 
-pub enum SRSValue<'a, G: CommitmentCurve> {
-    Value(SRS<G>),
-    Ref(&'a SRS<G>),
+// commitment key, owned or borrowed, of the IPA SRS by default
+pub enum SRSValue<'a, S> {
+    Value(S),
+    Ref(&'a S),
 }
 
-impl<'a, G: CommitmentCurve> SRSValue<'a, G> {
-    pub fn get_ref(&self) -> &SRS<G> {
+impl<'a, S> SRSValue<'a, S> {
+    pub fn get_ref(&self) -> &S {
         match self {
             SRSValue::Value(x) => &x,
             SRSValue::Ref(x) => x,
@@ -147,7 +547,7 @@ pub enum SRSSpec<'a, G: CommitmentCurve> {
     Generate(usize),
 }
 
-impl<'a, G: CommitmentCurve> SRSValue<'a, G>
+impl<'a, G: CommitmentCurve> SRSValue<'a, SRS<G>>
 where
     G::BaseField: PrimeField,
     G::ScalarField: CommitmentField,
@@ -156,7 +556,7 @@ where
         SRS::<G>::create(size)
     }
 
-    pub fn create<'b>(spec: SRSSpec<'a, G>) -> SRSValue<'a, G> {
+    pub fn create<'b>(spec: SRSSpec<'a, G>) -> SRSValue<'a, SRS<G>> {
         match spec {
             SRSSpec::Use(x) => SRSValue::Ref(x),
             SRSSpec::Generate(size) => SRSValue::Value(Self::generate(size)),
@@ -0,0 +1,114 @@
+/*****************************************************************************************************************
+
+This source file implements the validation of the points of untrusted data
+
+The points deserialized from SRS files, verifier indices and proofs are only decoded, the
+decoding does not check them to be on the curve. The multi-scalar multiplications of the
+verifier are meaningless for such points, so they are checked beforehand to be on the curve
+and in its prime order subgroup. The delta and challenge polynomial commitment of an opening
+proof have as well to be distinct from the identity, the commitments may be the identity as
+the commitments to zero are, and so may the L and R commitments of the rounds, which are not
+blinded by the non-hiding provers and vanish for the sparse polynomials.
+
+*****************************************************************************************************************/
+
+use crate::commitment::{CommitmentCurve, OpeningProof, PolyComm};
+use crate::srs::SRS;
+use algebra::Zero;
+pub use oracle::rndoracle::InvalidPoint;
+use rayon::prelude::*;
+use std::io::{Error, ErrorKind};
+
+// This function checks a point to be on the curve and in its prime order subgroup
+//     p: point to check
+//     location: description of the point for the error
+pub fn check_point<G: CommitmentCurve>(p: &G, location: &'static str) -> Result<(), InvalidPoint> {
+    if p.is_valid() {
+        Ok(())
+    } else {
+        Err(InvalidPoint::NotOnCurve(location))
+    }
+}
+
+// This function checks a point to be valid and distinct from the identity
+//     p: point to check
+//     location: description of the point for the error
+pub fn check_nonzero_point<G: CommitmentCurve>(
+    p: &G,
+    location: &'static str,
+) -> Result<(), InvalidPoint> {
+    if p.is_zero() {
+        Err(InvalidPoint::Identity(location))
+    } else {
+        check_point(p, location)
+    }
+}
+
+// This function checks in parallel a vector of points to be valid
+pub fn check_points<G: CommitmentCurve>(
+    points: &[G],
+    location: &'static str,
+) -> Result<(), InvalidPoint> {
+    points.par_iter().try_for_each(|p| check_point(p, location))
+}
+
+// This function checks in parallel a vector of points to be valid and distinct from the identity
+pub fn check_nonzero_points<G: CommitmentCurve>(
+    points: &[G],
+    location: &'static str,
+) -> Result<(), InvalidPoint> {
+    points
+        .par_iter()
+        .try_for_each(|p| check_nonzero_point(p, location))
+}
+
+// This function converts the invalid point of deserialized data to an I/O error
+pub(crate) fn invalid_data(e: InvalidPoint) -> Error {
+    Error::new(ErrorKind::InvalidData, e)
+}
+
+impl<G: CommitmentCurve> PolyComm<G> {
+    // This function checks the segments of the commitment to be valid, the identity included
+    //     location: description of the commitment for the error
+    pub fn validate(&self, location: &'static str) -> Result<(), InvalidPoint> {
+        check_points(&self.unshifted, location)?;
+        match &self.shifted {
+            Some(shifted) => check_point(shifted, location),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<G: CommitmentCurve> OpeningProof<G> {
+    // This function checks the points of the opening proof to be valid, and its delta and
+    // challenge polynomial commitment to be distinct from the identity
+    pub fn validate(&self) -> Result<(), InvalidPoint> {
+        self.lr.par_iter().try_for_each(|(l, r)| {
+            check_point(l, "opening proof L")?;
+            check_point(r, "opening proof R")
+        })?;
+        check_nonzero_point(&self.delta, "opening proof delta")?;
+        check_nonzero_point(&self.sg, "opening proof challenge polynomial commitment")
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G> {
+    // This function checks the generators of SRS to be valid and distinct from the identity,
+    // and the cached commitments to the Lagrange bases and fixed-base tables to be valid
+    pub fn validate(&self) -> Result<(), InvalidPoint> {
+        check_nonzero_points(&self.g, "SRS generator g")?;
+        check_nonzero_point(&self.h, "SRS generator h")?;
+        for basis in self.lagrange_bases.values() {
+            basis
+                .par_iter()
+                .try_for_each(|comm| comm.validate("SRS Lagrange basis commitment"))?;
+        }
+        match &self.fixed_base {
+            Some(table) => table
+                .multiples
+                .par_iter()
+                .try_for_each(|m| check_points(m, "SRS fixed-base table")),
+            None => Ok(()),
+        }
+    }
+}
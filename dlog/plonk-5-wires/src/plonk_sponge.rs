@@ -1,17 +1,30 @@
 use algebra::{Field, PrimeField};
+use oracle::keccak::KeccakFrSponge;
 use oracle::poseidon::{
     ArithmeticSponge, ArithmeticSpongeParams, PlonkSpongeConstants5W as SC, Sponge,
 };
 use oracle::sponge::{DefaultFrSponge, ScalarChallenge};
+use oracle::transcript::Transcript;
 use plonk_5_wires_circuits::scalars::ProofEvaluations;
 
 pub trait FrSponge<Fr: Field> {
     fn new(p: ArithmeticSpongeParams<Fr>) -> Self;
+    // This function labels the step of the argument, for the transcripts that record it
+    fn label(&mut self, _label: &'static str) {}
     fn absorb(&mut self, x: &Fr);
     fn challenge(&mut self) -> ScalarChallenge<Fr>;
     fn absorb_evaluations(&mut self, p: &[Fr], e: &ProofEvaluations<Vec<Fr>>);
 }
 
+// This function lists the evaluations of the polynomials of a proof in the order the sponges
+// absorb them
+fn evaluations<Fr>(e: &ProofEvaluations<Vec<Fr>>) -> [&Vec<Fr>; 12] {
+    [
+        &e.w[0], &e.w[1], &e.w[2], &e.w[3], &e.w[4], &e.z, &e.t, &e.f, &e.s[0], &e.s[1], &e.s[2],
+        &e.s[3],
+    ]
+}
+
 impl<Fr: PrimeField> FrSponge<Fr> for DefaultFrSponge<Fr, SC> {
     fn new(params: ArithmeticSpongeParams<Fr>) -> DefaultFrSponge<Fr, SC> {
         DefaultFrSponge {
@@ -32,14 +45,52 @@ impl<Fr: PrimeField> FrSponge<Fr> for DefaultFrSponge<Fr, SC> {
     fn absorb_evaluations(&mut self, p: &[Fr], e: &ProofEvaluations<Vec<Fr>>) {
         self.last_squeezed = vec![];
         self.sponge.absorb(p);
+        for e in evaluations(e).iter() {
+            self.sponge.absorb(e);
+        }
+    }
+}
 
-        let points = [
-            &e.w[0], &e.w[1], &e.w[2], &e.w[3], &e.w[4], &e.z, &e.t, &e.f, &e.s[0], &e.s[1],
-            &e.s[2], &e.s[3],
-        ];
+impl<Fr: PrimeField> FrSponge<Fr> for KeccakFrSponge<Fr> {
+    fn new(_params: ArithmeticSpongeParams<Fr>) -> KeccakFrSponge<Fr> {
+        KeccakFrSponge::create()
+    }
 
-        for p in &points {
-            self.sponge.absorb(p);
-        }
+    fn absorb(&mut self, x: &Fr) {
+        self.absorb_scalars(&[*x]);
+    }
+
+    fn challenge(&mut self) -> ScalarChallenge<Fr> {
+        self.squeeze_challenge()
+    }
+
+    fn absorb_evaluations(&mut self, p: &[Fr], e: &ProofEvaluations<Vec<Fr>>) {
+        self.absorb_proof_evaluations(p, &evaluations(e))
+    }
+}
+
+impl<Fr: Field, S: FrSponge<Fr>> FrSponge<Fr> for Transcript<S> {
+    fn new(params: ArithmeticSpongeParams<Fr>) -> Self {
+        Transcript::wrap(S::new(params))
+    }
+
+    fn label(&mut self, label: &'static str) {
+        self.set_label(label)
+    }
+
+    fn absorb(&mut self, x: &Fr) {
+        self.absorbed(&[*x]);
+        self.sponge.absorb(x)
+    }
+
+    fn challenge(&mut self) -> ScalarChallenge<Fr> {
+        let c = self.sponge.challenge();
+        self.squeezed(&c);
+        c
+    }
+
+    fn absorb_evaluations(&mut self, p: &[Fr], e: &ProofEvaluations<Vec<Fr>>) {
+        self.absorbed_evaluations(p, &evaluations(e));
+        self.sponge.absorb_evaluations(p, e)
     }
 }
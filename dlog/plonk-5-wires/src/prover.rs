@@ -6,10 +6,12 @@ This source file implements prover's zk-proof primitive.
 
 pub use super::{index::Index, range};
 use crate::plonk_sponge::FrSponge;
-use algebra::{AffineCurve, Field, One, UniformRand, Zero};
+use algebra::{AffineCurve, Field, FromBytes, One, ToBytes, UniformRand, Zero};
 use array_init::array_init;
-use commitment_dlog::commitment::{
-    b_poly_coefficients, CommitmentCurve, CommitmentField, OpeningProof, PolyComm,
+use commitment_dlog::{
+    commitment::{ChallengePolynomial, CommitmentCurve, OpeningProof, PolyComm},
+    scheme::PolynomialCommitment,
+    serialization::{read_fields, read_header, read_len, write_fields, write_header, write_len},
 };
 use ff_fft::{DensePolynomial, Evaluations, Radix2EvaluationDomain as D};
 use oracle::{rndoracle::ProofError, sponge::ScalarChallenge, utils::PolyUtils, FqSponge};
@@ -18,10 +20,15 @@ use plonk_5_wires_circuits::{
     wires::COLUMNS,
 };
 use rand::thread_rng;
+use rand_core::RngCore;
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
 
 type Fr<G> = <G as AffineCurve>::ScalarField;
 type Fq<G> = <G as AffineCurve>::BaseField;
 
+// magic tag of the binary proof encoding
+const PROOF_MAGIC: &[u8; 4] = b"PLK5";
+
 #[derive(Clone)]
 pub struct ProverCommitments<G: AffineCurve> {
     // polynomial commitments
@@ -87,12 +94,12 @@ struct CamlProverProof<G: AffineCurve> {
 }
 
 #[derive(Clone)]
-pub struct ProverProof<G: AffineCurve> {
+pub struct ProverProof<G: AffineCurve, P = OpeningProof<G>> {
     // polynomial commitments
     pub commitments: ProverCommitments<G>,
 
     // batched commitment opening proof
-    pub proof: OpeningProof<G>,
+    pub proof: P,
 
     // polynomial evaluations
     pub evals: [ProofEvaluations<Vec<Fr<G>>>; 2],
@@ -101,7 +108,12 @@ pub struct ProverProof<G: AffineCurve> {
     pub public: Vec<Fr<G>>,
 
     // The challenges underlying the optional polynomials folded into the proof
-    pub prev_challenges: Vec<(Vec<Fr<G>>, PolyComm<G>)>,
+    pub prev_challenges: Vec<(ChallengePolynomial<Fr<G>>, PolyComm<G>)>,
+
+    // whether the commitments are blinded, the proof being zero-knowledge. The marker is
+    // informational only: it is not absorbed into the transcript, which the verifiers of the
+    // proofs in circuits replicate, and the proof verifies the same whatever its value
+    pub hiding: bool,
 }
 
 #[cfg(feature = "ocaml_types")]
@@ -118,7 +130,11 @@ where
                 (evals0, evals1)
             },
             public: self.public,
-            prev_challenges: self.prev_challenges,
+            prev_challenges: self
+                .prev_challenges
+                .into_iter()
+                .map(|(b, comm)| (b.chals, comm))
+                .collect(),
         })
     }
 }
@@ -138,24 +154,191 @@ where
                 [evals0, evals1]
             },
             public: p.public,
-            prev_challenges: p.prev_challenges,
+            prev_challenges: p
+                .prev_challenges
+                .into_iter()
+                .map(|(chals, comm)| (ChallengePolynomial::new(chals), comm))
+                .collect(),
+            // the OCaml bindings create hiding proofs only
+            hiding: true,
         }
     }
 }
 
-impl<G: CommitmentCurve> ProverProof<G>
-where
-    G::ScalarField: CommitmentField,
-{
-    // This function constructs prover's zk-proof from the witness & the Index against SRS instance
+impl<G: AffineCurve> ToBytes for ProverCommitments<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut w: W) -> IoResult<()> {
+        for c in self.w_comm.iter() {
+            c.write(&mut w)?;
+        }
+        self.z_comm.write(&mut w)?;
+        self.t_comm.write(&mut w)
+    }
+}
+
+impl<G: AffineCurve> FromBytes for ProverCommitments<G> {
+    #[inline]
+    fn read<R: Read>(mut r: R) -> IoResult<Self> {
+        let mut w_comm = vec![];
+        for _ in 0..COLUMNS {
+            w_comm.push(PolyComm::read(&mut r)?);
+        }
+        Ok(ProverCommitments {
+            w_comm: array_init(|i| w_comm[i].clone()),
+            z_comm: PolyComm::read(&mut r)?,
+            t_comm: PolyComm::read(&mut r)?,
+        })
+    }
+}
+
+impl<G: AffineCurve> ToBytes for ProverProof<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut w: W) -> IoResult<()> {
+        write_header(PROOF_MAGIC, &mut w)?;
+        (self.hiding as u8).write(&mut w)?;
+        self.commitments.write(&mut w)?;
+        self.proof.write(&mut w)?;
+        self.evals[0].write(&mut w)?;
+        self.evals[1].write(&mut w)?;
+        write_fields(&self.public, &mut w)?;
+        write_len(self.prev_challenges.len(), &mut w)?;
+        for (b, comm) in self.prev_challenges.iter() {
+            write_fields(&b.chals, &mut w)?;
+            comm.write(&mut w)?;
+        }
+        Ok(())
+    }
+}
+
+impl<G: AffineCurve> FromBytes for ProverProof<G> {
+    #[inline]
+    fn read<R: Read>(mut r: R) -> IoResult<Self> {
+        read_header(PROOF_MAGIC, &mut r)?;
+        let hiding = match u8::read(&mut r)? {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::new(ErrorKind::InvalidData, "Invalid hiding marker")),
+        };
+        let commitments = ProverCommitments::read(&mut r)?;
+        let proof = OpeningProof::read(&mut r)?;
+        let evals = [
+            ProofEvaluations::read(&mut r)?,
+            ProofEvaluations::read(&mut r)?,
+        ];
+        let public = read_fields(&mut r)?;
+        let mut prev_challenges = vec![];
+        for _ in 0..read_len(&mut r)? {
+            let chals = read_fields(&mut r)?;
+            let comm = PolyComm::read(&mut r)?;
+            prev_challenges.push((ChallengePolynomial::new(chals), comm));
+        }
+        Ok(ProverProof {
+            commitments,
+            proof,
+            evals,
+            public,
+            prev_challenges,
+            hiding,
+        })
+    }
+}
+
+impl<G: CommitmentCurve, P> ProverProof<G, P> {
+    // This function constructs prover's zk-proof from the witness & the Index against the
+    // commitment key of any polynomial commitment scheme
     //     witness: computation witness
     //     index: Index
     //     RETURN: prover's zk-proof
-    pub fn create<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>, EFrSponge: FrSponge<Fr<G>>>(
+    pub fn create<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+        S: PolynomialCommitment<Curve = G, Proof = P>,
+    >(
         group_map: &G::Map,
         witness: &[Vec<Fr<G>>; COLUMNS],
-        index: &Index<G>,
-        prev_challenges: Vec<(Vec<Fr<G>>, PolyComm<G>)>,
+        index: &Index<G, S>,
+        prev_challenges: Vec<(ChallengePolynomial<Fr<G>>, PolyComm<G>)>,
+    ) -> Result<Self, ProofError> {
+        Self::create_with_rng::<EFqSponge, EFrSponge, S>(
+            group_map,
+            witness,
+            index,
+            prev_challenges,
+            &mut thread_rng(),
+        )
+    }
+
+    // This function constructs prover's zk-proof from the witness & the Index against SRS instance,
+    // drawing the blinding randomness from the given source, so that the proof is reproducible
+    //     witness: computation witness
+    //     index: Index
+    //     rng: randomness source context
+    //     RETURN: prover's zk-proof
+    pub fn create_with_rng<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+        S: PolynomialCommitment<Curve = G, Proof = P>,
+    >(
+        group_map: &G::Map,
+        witness: &[Vec<Fr<G>>; COLUMNS],
+        index: &Index<G, S>,
+        prev_challenges: Vec<(ChallengePolynomial<Fr<G>>, PolyComm<G>)>,
+        rng: &mut dyn RngCore,
+    ) -> Result<Self, ProofError> {
+        Self::prove::<EFqSponge, EFrSponge, S>(
+            group_map,
+            witness,
+            index,
+            prev_challenges,
+            true,
+            rng,
+        )
+    }
+
+    // This function constructs a proof which is not zero-knowledge, for the pipelines which need
+    // none: the commitments and the L and R commitments of the opening proof are not blinded
+    // and the rows of the permutation polynomial past the circuit are not randomized. The proof
+    // satisfies the same relation as the hiding ones and is verified the same way, the
+    // randomness source only drawing the nonces of delta of the opening proof. The zero-knowledge
+    // polynomial zkpm is not skipped: it stays in the permutation relation, which the verifier
+    // checks regardless of the informational marker, the rows it excludes being left as the
+    // permutation argument computes them
+    //     witness: computation witness
+    //     index: Index
+    //     rng: randomness source context
+    //     RETURN: prover's proof, marked as not hiding
+    pub fn create_non_hiding<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+        S: PolynomialCommitment<Curve = G, Proof = P>,
+    >(
+        group_map: &G::Map,
+        witness: &[Vec<Fr<G>>; COLUMNS],
+        index: &Index<G, S>,
+        prev_challenges: Vec<(ChallengePolynomial<Fr<G>>, PolyComm<G>)>,
+        rng: &mut dyn RngCore,
+    ) -> Result<Self, ProofError> {
+        Self::prove::<EFqSponge, EFrSponge, S>(
+            group_map,
+            witness,
+            index,
+            prev_challenges,
+            false,
+            rng,
+        )
+    }
+
+    fn prove<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+        S: PolynomialCommitment<Curve = G, Proof = P>,
+    >(
+        group_map: &G::Map,
+        witness: &[Vec<Fr<G>>; COLUMNS],
+        index: &Index<G, S>,
+        mut prev_challenges: Vec<(ChallengePolynomial<Fr<G>>, PolyComm<G>)>,
+        hiding: bool,
+        rng: &mut dyn RngCore,
     ) -> Result<Self, ProofError> {
         let n = index.cs.domain.d1.size as usize;
         for w in witness.iter() {
@@ -178,8 +361,6 @@ where
         )
         .interpolate();
 
-        let rng = &mut thread_rng();
-
         // compute witness polynomials
         let w: [DensePolynomial<Fr<G>>; COLUMNS] = array_init(|i| {
             Evaluations::<Fr<G>, D<Fr<G>>>::from_vec_and_domain(
@@ -190,11 +371,17 @@ where
         });
 
         // commit to the wire values
-        let w_comm: [(PolyComm<G>, PolyComm<Fr<G>>); COLUMNS] =
-            array_init(|i| index.srs.get_ref().commit(&w[i], None, rng));
+        let w_comm: [(PolyComm<G>, PolyComm<Fr<G>>); COLUMNS] = array_init(|i| {
+            index
+                .srs
+                .get_ref()
+                .commit_maybe_hiding(&w[i], None, hiding, rng)
+        });
 
         // absorb the wire polycommitments into the argument
-        fq_sponge.absorb_g(&index.srs.get_ref().commit_non_hiding(&p, None).unshifted);
+        let public_input_comm = index.srs.get_ref().commit_non_hiding(&p, None).unshifted;
+        fq_sponge.label("witness");
+        fq_sponge.absorb_g(&public_input_comm);
         w_comm
             .iter()
             .for_each(|c| fq_sponge.absorb_g(&c.0.unshifted));
@@ -226,18 +413,24 @@ where
         if z[n - 3] != Fr::<G>::one() {
             return Err(ProofError::ProofCreation);
         };
-        z[n - 2] = Fr::<G>::rand(rng);
-        z[n - 1] = Fr::<G>::rand(rng);
+        if hiding {
+            z[n - 2] = Fr::<G>::rand(rng);
+            z[n - 1] = Fr::<G>::rand(rng);
+        }
         let z = Evaluations::<Fr<G>, D<Fr<G>>>::from_vec_and_domain(z, index.cs.domain.d1)
             .interpolate();
 
         // commit to z
-        let z_comm = index.srs.get_ref().commit(&z, None, rng);
+        let z_comm = index
+            .srs
+            .get_ref()
+            .commit_maybe_hiding(&z, None, hiding, rng);
 
         // absorb the z commitment into the argument and query alpha
+        fq_sponge.label("permutation");
         fq_sponge.absorb_g(&z_comm.0.unshifted);
         oracles.alpha_chal = ScalarChallenge(fq_sponge.challenge());
-        oracles.alpha = oracles.alpha_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.alpha = oracles.alpha_chal.to_field(&index.endo_r);
         let alpha = range::alpha_powers(oracles.alpha);
 
         // evaluate polynomials over domains
@@ -284,27 +477,21 @@ where
         t += &bnd;
 
         // commit to t
-        let t_comm = index
-            .srs
-            .get_ref()
-            .commit(&t, Some(index.max_quot_size), rng);
+        let t_comm =
+            index
+                .srs
+                .get_ref()
+                .commit_maybe_hiding(&t, Some(index.max_quot_size), hiding, rng);
 
         // absorb the polycommitments into the argument and sample zeta
         let max_t_size = (index.max_quot_size + index.max_poly_size - 1) / index.max_poly_size;
-        let dummy = G::of_coordinates(Fq::<G>::zero(), Fq::<G>::zero());
+        fq_sponge.label("quotient");
         fq_sponge.absorb_g(&t_comm.0.unshifted);
-        fq_sponge.absorb_g(&vec![dummy; max_t_size - t_comm.0.unshifted.len()]);
-        {
-            let s = t_comm.0.shifted.unwrap();
-            if s.is_zero() {
-                fq_sponge.absorb_g(&[dummy])
-            } else {
-                fq_sponge.absorb_g(&[s])
-            }
-        };
+        fq_sponge.absorb_g(&vec![G::zero(); max_t_size - t_comm.0.unshifted.len()]);
+        fq_sponge.absorb_g(&[t_comm.0.shifted.unwrap()]);
 
         oracles.zeta_chal = ScalarChallenge(fq_sponge.challenge());
-        oracles.zeta = oracles.zeta_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.zeta = oracles.zeta_chal.to_field(&index.endo_r);
 
         // evaluate the polynomials
         let evlp = [oracles.zeta, oracles.zeta * &index.cs.domain.d1.group_gen];
@@ -354,8 +541,10 @@ where
         evals[1].f = f.eval(evlp[1], index.max_poly_size);
 
         let fq_sponge_before_evaluations = fq_sponge.clone();
+        fq_sponge.label("evaluations");
         let mut fr_sponge = {
             let mut s = EFrSponge::new(index.cs.fr_sponge_params.clone());
+            s.label("evaluations");
             s.absorb(&fq_sponge.digest());
             s
         };
@@ -370,17 +559,17 @@ where
 
         // query opening scaler challenges
         oracles.v_chal = fr_sponge.challenge();
-        oracles.v = oracles.v_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.v = oracles.v_chal.to_field(&index.endo_r);
         oracles.u_chal = fr_sponge.challenge();
-        oracles.u = oracles.u_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.u = oracles.u_chal.to_field(&index.endo_r);
 
         // construct the proof
         // --------------------------------------------------------------------
         let polys = prev_challenges
-            .iter()
-            .map(|(chals, comm)| {
+            .iter_mut()
+            .map(|(b, comm)| {
                 (
-                    DensePolynomial::from_coefficients_vec(b_poly_coefficients(chals)),
+                    DensePolynomial::from_coefficients_slice(b.coefficients()),
                     comm.unshifted.len(),
                 )
             })
@@ -394,7 +583,7 @@ where
             .iter()
             .map(|(p, n)| (p, None, non_hiding(*n)))
             .collect::<Vec<_>>();
-        polynoms.extend(vec![(&p, None, non_hiding(1))]);
+        polynoms.extend(vec![(&p, None, non_hiding(public_input_comm.len()))]);
         polynoms.extend(
             w.iter()
                 .zip(w_comm.iter())
@@ -416,18 +605,20 @@ where
                 z_comm: z_comm.0,
                 t_comm: t_comm.0,
             },
-            proof: index.srs.get_ref().open(
+            proof: index.srs.get_ref().open_maybe_hiding(
                 group_map,
                 polynoms,
                 &evlp.to_vec(),
                 oracles.v,
                 oracles.u,
                 fq_sponge_before_evaluations,
+                hiding,
                 rng,
             ),
             evals,
             public,
             prev_challenges,
+            hiding,
         })
     }
 }
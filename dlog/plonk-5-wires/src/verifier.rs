@@ -8,11 +8,20 @@ pub use super::index::VerifierIndex as Index;
 pub use super::prover::{range, ProverProof};
 use crate::plonk_sponge::FrSponge;
 use algebra::{AffineCurve, Field, One, Zero};
-use commitment_dlog::commitment::{
-    b_poly, b_poly_coefficients, combined_inner_product, CommitmentCurve, CommitmentField, PolyComm,
+use commitment_dlog::{
+    commitment::{
+        ceil_log2, combined_inner_product, CommitmentCurve, CommitmentField, PolyComm,
+        PolyCommCombination,
+    },
+    scheme::PolynomialCommitment,
+    validation::InvalidPoint,
 };
 use ff_fft::EvaluationDomain;
-use oracle::{rndoracle::ProofError, sponge::ScalarChallenge, FqSponge};
+use oracle::{
+    rndoracle::{FailedCheck, ProofError},
+    sponge::ScalarChallenge,
+    FqSponge,
+};
 use plonk_5_wires_circuits::{
     constraints::ConstraintSystem, scalars::RandomOracles, wires::COLUMNS,
 };
@@ -21,48 +30,94 @@ use rand::thread_rng;
 type Fr<G> = <G as AffineCurve>::ScalarField;
 type Fq<G> = <G as AffineCurve>::BaseField;
 
-impl<G: CommitmentCurve> ProverProof<G>
-where
-    G::ScalarField: CommitmentField,
-{
-    pub fn prev_chal_evals(
+impl<G: CommitmentCurve, P> ProverProof<G, P> {
+    // This function checks the shape of the proof to be the one the index expects, the points of
+    // the proof to be on the curve, the points of its opening proof to pass the checks of the
+    // commitment scheme of the index, and its previous challenges to be no more than the rounds
+    // of an opening proof against the SRS of the index, before any of them is used
+    //     index: verifier index the proof is verified against
+    pub fn validate<S: PolynomialCommitment<Curve = G, Proof = P>>(
         &self,
-        index: &Index<G>,
+        index: &Index<G, S>,
+    ) -> Result<(), ProofError> {
+        let rounds = ceil_log2(index.max_poly_size);
+        if self
+            .prev_challenges
+            .iter()
+            .any(|(b, _)| b.chals.len() > rounds)
+        {
+            return Err(ProofError::PrevChallenges);
+        }
+        self.validate_shape(index)?;
+        self.validate_points(index.srs.get_ref())
+            .map_err(ProofError::InvalidPoint)
+    }
+
+    // This function checks the quotient commitment to be shifted and of no more segments than the
+    // quotient of the index has, and the evaluations of every polynomial at both evaluation points
+    // to be of the same number of segments
+    fn validate_shape<S>(&self, index: &Index<G, S>) -> Result<(), ProofError> {
+        let t_comm = &self.commitments.t_comm;
+        if t_comm.shifted.is_none() {
+            return Err(ProofError::ProofShape("quotient commitment"));
+        }
+        let max_t_size = (index.max_quot_size + index.max_poly_size - 1) / index.max_poly_size;
+        if t_comm.unshifted.len() > max_t_size {
+            return Err(ProofError::ProofShape("quotient commitment"));
+        }
+        let evals = &self.evals;
+        let lengths_match = evals[0]
+            .w
+            .iter()
+            .zip(evals[1].w.iter())
+            .chain(evals[0].s.iter().zip(evals[1].s.iter()))
+            .chain(vec![
+                (&evals[0].z, &evals[1].z),
+                (&evals[0].t, &evals[1].t),
+                (&evals[0].f, &evals[1].f),
+            ])
+            .all(|(e0, e1)| e0.len() == e1.len());
+        if !lengths_match {
+            return Err(ProofError::ProofShape("proof evaluations"));
+        }
+        Ok(())
+    }
+
+    fn validate_points<S: PolynomialCommitment<Curve = G, Proof = P>>(
+        &self,
+        srs: &S,
+    ) -> Result<(), InvalidPoint> {
+        let commitments = &self.commitments;
+        let others = vec![&commitments.z_comm, &commitments.t_comm];
+        for comm in commitments.w_comm.iter().chain(others) {
+            comm.validate("proof commitment")?;
+        }
+        for (_, comm) in self.prev_challenges.iter() {
+            comm.validate("previous challenge polynomial commitment")?;
+        }
+        srs.validate_proof(&self.proof)
+    }
+
+    pub fn prev_chal_evals<S>(
+        &self,
+        index: &Index<G, S>,
         evaluation_points: &[Fr<G>],
         evlp: &[Fr<G>],
     ) -> Vec<Vec<Vec<Fr<G>>>> {
         self.prev_challenges
             .iter()
-            .map(|(chals, _poly)| {
+            .map(|(b, _poly)| {
                 // No need to check the correctness of poly explicitly. Its correctness is assured by the
                 // checking of the inner product argument.
-                let b_len = 1 << chals.len();
-                let mut b: Option<Vec<Fr<G>>> = None;
-
                 (0..2)
                     .map(|i| {
-                        let full = b_poly(&chals, evaluation_points[i]);
-                        if index.max_poly_size == b_len {
+                        let full = b.evaluate(evaluation_points[i]);
+                        if index.max_poly_size == b.size() {
                             return vec![full];
                         }
-                        let mut betaacc = Fr::<G>::one();
-                        let diff = (index.max_poly_size..b_len)
-                            .map(|j| {
-                                let b_j = match &b {
-                                    None => {
-                                        let t = b_poly_coefficients(&chals);
-                                        let res = t[j];
-                                        b = Some(t);
-                                        res
-                                    }
-                                    Some(b) => b[j],
-                                };
-
-                                let ret = betaacc * &b_j;
-                                betaacc *= &evaluation_points[i];
-                                ret
-                            })
-                            .fold(Fr::<G>::zero(), |x, y| x + &y);
+                        // the coefficients past the segment size, evaluated without computing
+                        // the coefficients of the polynomial
+                        let diff = b.evaluate_tail(index.max_poly_size, evaluation_points[i]);
                         vec![full - &(diff * &evlp[i]), diff]
                     })
                     .collect()
@@ -71,9 +126,9 @@ where
     }
 
     // This function runs random oracle argument
-    pub fn oracles<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>, EFrSponge: FrSponge<Fr<G>>>(
+    pub fn oracles<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>, EFrSponge: FrSponge<Fr<G>>, S>(
         &self,
-        index: &Index<G>,
+        index: &Index<G, S>,
         p_comm: &PolyComm<G>,
     ) -> (
         EFqSponge,
@@ -91,6 +146,7 @@ where
         let mut oracles = RandomOracles::<Fr<G>>::zero();
         let mut fq_sponge = EFqSponge::new(index.fq_sponge_params.clone());
         // absorb the public input, l, r, o polycommitments into the argument
+        fq_sponge.label("witness");
         fq_sponge.absorb_g(&p_comm.unshifted);
         self.commitments
             .w_comm
@@ -100,31 +156,30 @@ where
         oracles.beta = fq_sponge.challenge();
         oracles.gamma = fq_sponge.challenge();
         // absorb the z commitment into the argument and query alpha
+        fq_sponge.label("permutation");
         fq_sponge.absorb_g(&self.commitments.z_comm.unshifted);
         oracles.alpha_chal = ScalarChallenge(fq_sponge.challenge());
-        oracles.alpha = oracles.alpha_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.alpha = oracles.alpha_chal.to_field(&index.endo_r);
         // absorb the polycommitments into the argument and sample zeta
         let max_t_size = (index.max_quot_size + index.max_poly_size - 1) / index.max_poly_size;
-        let dummy = G::of_coordinates(Fq::<G>::zero(), Fq::<G>::zero());
+        fq_sponge.label("quotient");
         fq_sponge.absorb_g(&self.commitments.t_comm.unshifted);
         fq_sponge.absorb_g(&vec![
-            dummy;
+            G::zero();
             max_t_size - self.commitments.t_comm.unshifted.len()
         ]);
-        {
-            let s = self.commitments.t_comm.shifted.unwrap();
-            if s.is_zero() {
-                fq_sponge.absorb_g(&[dummy])
-            } else {
-                fq_sponge.absorb_g(&[s])
-            }
-        };
+        fq_sponge.absorb_g(&[self.commitments.t_comm.shifted.unwrap()]);
 
         oracles.zeta_chal = ScalarChallenge(fq_sponge.challenge());
-        oracles.zeta = oracles.zeta_chal.to_field(&index.srs.get_ref().endo_r);
-        let digest = fq_sponge.clone().digest();
+        oracles.zeta = oracles.zeta_chal.to_field(&index.endo_r);
+        let digest = {
+            let mut s = fq_sponge.clone();
+            s.label("evaluations");
+            s.digest()
+        };
         let mut fr_sponge = {
             let mut s = EFrSponge::new(index.fr_sponge_params.clone());
+            s.label("evaluations");
             s.absorb(&digest);
             s
         };
@@ -181,9 +236,9 @@ where
 
         // query opening scaler challenges
         oracles.v_chal = fr_sponge.challenge();
-        oracles.v = oracles.v_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.v = oracles.v_chal.to_field(&index.endo_r);
         oracles.u_chal = fr_sponge.challenge();
-        oracles.u = oracles.u_chal.to_field(&index.srs.get_ref().endo_r);
+        oracles.u = oracles.u_chal.to_field(&index.endo_r);
 
         let ep = [oracles.zeta, zetaw];
 
@@ -224,13 +279,7 @@ where
                 Some(index.max_quot_size),
             )]);
 
-            combined_inner_product::<G>(
-                &ep,
-                &oracles.v,
-                &oracles.u,
-                &es,
-                index.srs.get_ref().g.len(),
-            )
+            combined_inner_product::<G>(&ep, &oracles.v, &oracles.u, &es, index.max_poly_size)
         };
 
         (
@@ -246,221 +295,379 @@ where
         )
     }
 
+    // This function checks the linearization/quotient identity of the proof
+    //     index: Index
+    //     lgr_comm: commitments to the Lagrange basis of the domain
+    //     RETURN: the values the opening proof is verified with
+    fn check_linearization<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+        S,
+    >(
+        &self,
+        index: &Index<G, S>,
+        lgr_comm: &Vec<PolyComm<G>>,
+    ) -> Result<OpeningParams<G, EFqSponge>, ProofError> {
+        // commit to public input polynomial
+        let p_comm = PolyComm::<G>::multi_scalar_mul(
+            &lgr_comm.iter().take(self.public.len()).map(|l| l).collect(),
+            &self.public.iter().map(|s| -*s).collect(),
+        );
+
+        let (fq_sponge, _, oracles, alpha, p_eval, evlp, polys, zeta1, _) =
+            self.oracles::<EFqSponge, EFrSponge, S>(index, &p_comm);
+
+        // evaluate committed polynoms
+        let evals = (0..2)
+            .map(|i| self.evals[i].combine(evlp[i]))
+            .collect::<Vec<_>>();
+
+        // compute linearization polynomial commitment
+        let mut f_comm = PolyCommCombination::new();
+
+        // permutation
+        let zkp = index.zkpm.evaluate(oracles.zeta);
+        f_comm.push(
+            &index.sigma_comm[COLUMNS - 1],
+            ConstraintSystem::perm_scalars(&evals, &oracles, zkp),
+        );
+
+        // generic
+        f_comm.extend(
+            vec![&index.qm_comm]
+                .into_iter()
+                .chain(index.qw_comm.iter())
+                .chain(vec![&index.qc_comm]),
+            ConstraintSystem::gnrc_scalars(&evals[0]),
+        );
+
+        // poseidon
+        f_comm.extend(
+            vec![&index.psm_comm]
+                .into_iter()
+                .chain(index.rcm_comm.iter()),
+            ConstraintSystem::psdn_scalars(&evals, &index.fr_sponge_params, &alpha[range::PSDN]),
+        );
+
+        // EC addition
+        f_comm.push(
+            &index.add_comm,
+            ConstraintSystem::ecad_scalars(&evals, &alpha[range::ADD]),
+        );
+
+        // EC doubling
+        f_comm.push(
+            &index.double_comm,
+            ConstraintSystem::double_scalars(&evals, &alpha[range::DBL]),
+        );
+
+        // variable base endoscalar multiplication
+        f_comm.push(
+            &index.emul_comm,
+            ConstraintSystem::endomul_scalars(&evals, index.endo, &alpha[range::ENDML]),
+        );
+
+        // packing
+        f_comm.push(
+            &index.pack_comm,
+            ConstraintSystem::pack_scalars(&evals, &alpha[range::PACK]),
+        );
+
+        // EC variable base scalar multiplication
+        f_comm.push(
+            &index.mul1_comm,
+            ConstraintSystem::vbmul_scalars(&evals, &alpha[range::MUL]),
+        );
+        f_comm.push(
+            &index.mul2_comm,
+            ConstraintSystem::vbmulpck_scalars(&evals, &alpha[range::MLPCK]),
+        );
+
+        let f_comm = f_comm.eval();
+
+        // check linearization polynomial evaluation consistency
+        let zeta1m1 = zeta1 - &Fr::<G>::one();
+        if (evals[0].f
+            + &(if p_eval[0].len() > 0 {
+                p_eval[0][0]
+            } else {
+                Fr::<G>::zero()
+            })
+            - evals[0]
+                .w
+                .iter()
+                .zip(evals[0].s.iter())
+                .map(|(w, s)| (oracles.beta * s) + w + &oracles.gamma)
+                .fold(
+                    (evals[0].w[COLUMNS - 1] + &oracles.gamma)
+                        * &evals[1].z
+                        * &oracles.alpha
+                        * &zkp,
+                    |x, y| x * y,
+                )
+            + evals[0]
+                .w
+                .iter()
+                .zip(index.shift.iter())
+                .map(|(w, s)| oracles.gamma + &(oracles.beta * &oracles.zeta * s) + w)
+                .fold(oracles.alpha * &zkp * &evals[0].z, |x, y| x * y)
+            - evals[0].t * &zeta1m1)
+            * &(oracles.zeta - &index.w)
+            * &(oracles.zeta - &Fr::<G>::one())
+            != ((zeta1m1 * &alpha[range::PERM][0] * &(oracles.zeta - &index.w))
+                + (zeta1m1 * &alpha[range::PERM][1] * &(oracles.zeta - &Fr::<G>::one())))
+                * &(Fr::<G>::one() - evals[0].z)
+        {
+            return Err(ProofError::ProofVerification);
+        }
+
+        Ok(OpeningParams {
+            p_eval,
+            p_comm,
+            f_comm,
+            fq_sponge,
+            oracles,
+            polys,
+        })
+    }
+
+    // This function prepares the verification of the opening proof of the proof
+    //     index: Index
+    //     params: values computed while checking the linearization
+    //     RETURN: the opening proof with its commitments and evaluations, as SRS verifies it
+    fn opening<'a, EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>, S>(
+        &'a self,
+        index: &'a Index<G, S>,
+        params: &'a OpeningParams<G, EFqSponge>,
+    ) -> (
+        EFqSponge,
+        Vec<Fr<G>>,
+        Fr<G>,
+        Fr<G>,
+        Vec<(&'a PolyComm<G>, Vec<&'a Vec<Fr<G>>>, Option<usize>)>,
+        &'a P,
+    ) {
+        let OpeningParams {
+            p_eval,
+            p_comm,
+            f_comm,
+            fq_sponge,
+            oracles,
+            polys,
+        } = params;
+
+        let mut polynoms = polys
+            .iter()
+            .map(|(comm, evals)| (comm, evals.iter().map(|x| x).collect(), None))
+            .collect::<Vec<(&PolyComm<G>, Vec<&Vec<Fr<G>>>, Option<usize>)>>();
+
+        polynoms.extend(vec![(
+            p_comm,
+            p_eval.iter().map(|e| e).collect::<Vec<_>>(),
+            None,
+        )]);
+        polynoms.extend(
+            proof
+                .commitments
+                .w_comm
+                .iter()
+                .zip(
+                    (0..COLUMNS)
+                        .map(|i| self.evals.iter().map(|e| &e.w[i]).collect::<Vec<_>>())
+                        .collect::<Vec<_>>()
+                        .iter(),
+                )
+                .map(|(c, e)| (c, e.clone(), None))
+                .collect::<Vec<_>>(),
+        );
+        polynoms.extend(vec![
+            (
+                &self.commitments.z_comm,
+                self.evals.iter().map(|e| &e.z).collect::<Vec<_>>(),
+                None,
+            ),
+            (
+                f_comm,
+                self.evals.iter().map(|e| &e.f).collect::<Vec<_>>(),
+                None,
+            ),
+        ]);
+        polynoms.extend(
+            index
+                .sigma_comm
+                .iter()
+                .zip(
+                    (0..COLUMNS - 1)
+                        .map(|i| self.evals.iter().map(|e| &e.s[i]).collect::<Vec<_>>())
+                        .collect::<Vec<_>>()
+                        .iter(),
+                )
+                .map(|(c, e)| (c, e.clone(), None))
+                .collect::<Vec<_>>(),
+        );
+        polynoms.extend(vec![(
+            &self.commitments.t_comm,
+            self.evals.iter().map(|e| &e.t).collect::<Vec<_>>(),
+            Some(index.max_quot_size),
+        )]);
+
+        // prepare for the opening proof verification
+        (
+            fq_sponge.clone(),
+            vec![oracles.zeta, oracles.zeta * &index.domain.group_gen],
+            oracles.v,
+            oracles.u,
+            polynoms,
+            &self.proof,
+        )
+    }
+
     // This function verifies the batch of zk-proofs
     //     proofs: vector of Plonk proofs
     //     index: Index
     //     RETURN: verification status
-    pub fn verify<EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>, EFrSponge: FrSponge<Fr<G>>>(
+    pub fn verify<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+        S: PolynomialCommitment<Curve = G, Proof = P>,
+    >(
         group_map: &G::Map,
-        proofs: &Vec<(&Index<G>, &Vec<PolyComm<G>>, &ProverProof<G>)>,
+        proofs: &Vec<(&Index<G, S>, &Vec<PolyComm<G>>, &ProverProof<G, P>)>,
     ) -> Result<bool, ProofError> {
         if proofs.len() == 0 {
             return Ok(true);
         }
+        validate_batch(proofs)?;
 
         let params = proofs
             .iter()
             .map(|(index, lgr_comm, proof)| {
-                // commit to public input polynomial
-                let p_comm = PolyComm::<G>::multi_scalar_mul(
-                    &lgr_comm
-                        .iter()
-                        .take(proof.public.len())
-                        .map(|l| l)
-                        .collect(),
-                    &proof.public.iter().map(|s| -*s).collect(),
-                );
-
-                let (fq_sponge, _, oracles, alpha, p_eval, evlp, polys, zeta1, _) =
-                    proof.oracles::<EFqSponge, EFrSponge>(index, &p_comm);
-
-                // evaluate committed polynoms
-                let evals = (0..2)
-                    .map(|i| proof.evals[i].combine(evlp[i]))
-                    .collect::<Vec<_>>();
-
-                // compute linearization polynomial commitment
-
-                // permutation
-                let zkp = index.zkpm.evaluate(oracles.zeta);
-                let mut p = vec![&index.sigma_comm[COLUMNS - 1]];
-                let mut s = vec![ConstraintSystem::perm_scalars(&evals, &oracles, zkp)];
-
-                // generic
-                p.push(&index.qm_comm);
-                p.extend(index.qw_comm.iter().map(|c| c).collect::<Vec<_>>());
-                p.push(&index.qc_comm);
-                s.extend(&ConstraintSystem::gnrc_scalars(&evals[0]));
-
-                // poseidon
-                s.extend(&ConstraintSystem::psdn_scalars(
-                    &evals,
-                    &index.fr_sponge_params,
-                    &alpha[range::PSDN],
-                ));
-                p.push(&index.psm_comm);
-                p.extend(index.rcm_comm.iter().map(|c| c).collect::<Vec<_>>());
-
-                // EC addition
-                s.push(ConstraintSystem::ecad_scalars(&evals, &alpha[range::ADD]));
-                p.push(&index.add_comm);
-
-                // EC doubling
-                s.push(ConstraintSystem::double_scalars(&evals, &alpha[range::DBL]));
-                p.push(&index.double_comm);
-
-                // variable base endoscalar multiplication
-                s.push(ConstraintSystem::endomul_scalars(
-                    &evals,
-                    index.endo,
-                    &alpha[range::ENDML],
-                ));
-                p.push(&index.emul_comm);
-
-                // packing
-                s.push(ConstraintSystem::pack_scalars(&evals, &alpha[range::PACK]));
-                p.push(&index.pack_comm);
-
-                // EC variable base scalar multiplication
-                s.push(ConstraintSystem::vbmul_scalars(&evals, &alpha[range::MUL]));
-                s.push(ConstraintSystem::vbmulpck_scalars(
-                    &evals,
-                    &alpha[range::MLPCK],
-                ));
-                p.extend([&index.mul1_comm, &index.mul2_comm].to_vec());
-
-                let f_comm = PolyComm::multi_scalar_mul(&p, &s);
-
-                // check linearization polynomial evaluation consistency
-                let zeta1m1 = zeta1 - &Fr::<G>::one();
-                if (evals[0].f
-                    + &(if p_eval[0].len() > 0 {
-                        p_eval[0][0]
-                    } else {
-                        Fr::<G>::zero()
-                    })
-                    - evals[0]
-                        .w
-                        .iter()
-                        .zip(evals[0].s.iter())
-                        .map(|(w, s)| (oracles.beta * s) + w + &oracles.gamma)
-                        .fold(
-                            (evals[0].w[COLUMNS - 1] + &oracles.gamma)
-                                * &evals[1].z
-                                * &oracles.alpha
-                                * &zkp,
-                            |x, y| x * y,
-                        )
-                    + evals[0]
-                        .w
-                        .iter()
-                        .zip(index.shift.iter())
-                        .map(|(w, s)| oracles.gamma + &(oracles.beta * &oracles.zeta * s) + w)
-                        .fold(oracles.alpha * &zkp * &evals[0].z, |x, y| x * y)
-                    - evals[0].t * &zeta1m1)
-                    * &(oracles.zeta - &index.w)
-                    * &(oracles.zeta - &Fr::<G>::one())
-                    != ((zeta1m1 * &alpha[range::PERM][0] * &(oracles.zeta - &index.w))
-                        + (zeta1m1 * &alpha[range::PERM][1] * &(oracles.zeta - &Fr::<G>::one())))
-                        * &(Fr::<G>::one() - evals[0].z)
-                {
-                    return Err(ProofError::ProofVerification);
-                }
-
-                Ok((p_eval, p_comm, f_comm, fq_sponge, oracles, polys))
+                proof.check_linearization::<EFqSponge, EFrSponge, S>(index, lgr_comm)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
         let mut batch = proofs
             .iter()
             .zip(params.iter())
-            .map(
-                |(
-                    (index, _lgr_comm, proof),
-                    (p_eval, p_comm, f_comm, fq_sponge, oracles, polys),
-                )| {
-                    let mut polynoms = polys
-                        .iter()
-                        .map(|(comm, evals)| (comm, evals.iter().map(|x| x).collect(), None))
-                        .collect::<Vec<(&PolyComm<G>, Vec<&Vec<Fr<G>>>, Option<usize>)>>();
-
-                    polynoms.extend(vec![(
-                        p_comm,
-                        p_eval.iter().map(|e| e).collect::<Vec<_>>(),
-                        None,
-                    )]);
-                    polynoms.extend(
-                        proof
-                            .commitments
-                            .w_comm
-                            .iter()
-                            .zip(
-                                (0..COLUMNS)
-                                    .map(|i| {
-                                        proof.evals.iter().map(|e| &e.w[i]).collect::<Vec<_>>()
-                                    })
-                                    .collect::<Vec<_>>()
-                                    .iter(),
-                            )
-                            .map(|(c, e)| (c, e.clone(), None))
-                            .collect::<Vec<_>>(),
-                    );
-                    polynoms.extend(vec![
-                        (
-                            &proof.commitments.z_comm,
-                            proof.evals.iter().map(|e| &e.z).collect::<Vec<_>>(),
-                            None,
-                        ),
-                        (
-                            f_comm,
-                            proof.evals.iter().map(|e| &e.f).collect::<Vec<_>>(),
-                            None,
-                        ),
-                    ]);
-                    polynoms.extend(
-                        index
-                            .sigma_comm
-                            .iter()
-                            .zip(
-                                (0..COLUMNS - 1)
-                                    .map(|i| {
-                                        proof.evals.iter().map(|e| &e.s[i]).collect::<Vec<_>>()
-                                    })
-                                    .collect::<Vec<_>>()
-                                    .iter(),
-                            )
-                            .map(|(c, e)| (c, e.clone(), None))
-                            .collect::<Vec<_>>(),
-                    );
-                    polynoms.extend(vec![(
-                        &proof.commitments.t_comm,
-                        proof.evals.iter().map(|e| &e.t).collect::<Vec<_>>(),
-                        Some(index.max_quot_size),
-                    )]);
-
-                    // prepare for the opening proof verification
-                    (
-                        fq_sponge.clone(),
-                        vec![oracles.zeta, oracles.zeta * &index.domain.group_gen],
-                        oracles.v,
-                        oracles.u,
-                        polynoms,
-                        &proof.proof,
-                    )
-                },
-            )
+            .map(|((index, _, proof), params)| proof.opening(index, params))
             .collect::<Vec<_>>();
 
         // verify the opening proofs
-        // TODO: Account for the different SRS lengths
-        let srs = proofs[0].0.srs.get_ref();
-        for (index, _, _) in proofs.iter() {
-            assert_eq!(index.srs.get_ref().g.len(), srs.g.len());
-        }
-
-        match srs.verify::<EFqSponge>(group_map, &mut batch, &mut thread_rng()) {
+        let (srs, srs_lengths) = batch_srs(proofs)?;
+        match srs.verify_prefixes::<EFqSponge>(
+            group_map,
+            &mut batch,
+            &srs_lengths,
+            &mut thread_rng(),
+        ) {
             false => Err(ProofError::OpenProof),
             true => Ok(true),
         }
     }
 }
+
+// The failures of a batch are found bisecting the batch of opening proofs, which only the inner
+// product argument supports
+impl<G: CommitmentCurve> ProverProof<G>
+where
+    G::ScalarField: CommitmentField,
+{
+    // This function verifies the batch of zk-proofs and reports the failing ones,
+    // bisecting the batch of opening proofs when their batched verification fails
+    //     proofs: vector of Plonk proofs
+    //     RETURN: indices of the failing proofs with the check they fail, empty if the batch verifies
+    pub fn verify_failures<
+        EFqSponge: Clone + FqSponge<Fq<G>, G, Fr<G>>,
+        EFrSponge: FrSponge<Fr<G>>,
+    >(
+        group_map: &G::Map,
+        proofs: &Vec<(&Index<G>, &Vec<PolyComm<G>>, &ProverProof<G>)>,
+    ) -> Result<Vec<(usize, FailedCheck)>, ProofError> {
+        if proofs.len() == 0 {
+            return Ok(vec![]);
+        }
+        let (srs, srs_lengths) = batch_srs(proofs)?;
+
+        // the proofs failing the validation or the linearization check have no opening to verify
+        let mut failures = vec![];
+        let mut opened = vec![];
+        let mut params = vec![];
+        for (i, (index, lgr_comm, proof)) in proofs.iter().enumerate() {
+            if proof.validate(index).is_err() {
+                failures.push((i, FailedCheck::Validation));
+                continue;
+            }
+            match proof.check_linearization::<EFqSponge, EFrSponge, _>(index, lgr_comm) {
+                Err(_) => failures.push((i, FailedCheck::Linearization)),
+                Ok(p) => {
+                    opened.push(i);
+                    params.push(p);
+                }
+            }
+        }
+        let batch = opened
+            .iter()
+            .zip(params.iter())
+            .map(|(i, params)| proofs[*i].2.opening(proofs[*i].0, params))
+            .collect::<Vec<_>>();
+        let lengths = opened.iter().map(|i| srs_lengths[*i]).collect::<Vec<_>>();
+
+        failures.extend(
+            srs.verify_failures::<EFqSponge>(group_map, &batch, &lengths, &mut thread_rng())
+                .into_iter()
+                .map(|(j, check)| (opened[j], check)),
+        );
+        failures.sort_by_key(|(i, _)| *i);
+        Ok(failures)
+    }
+}
+
+// The values the opening proof of a proof is verified with
+struct OpeningParams<G: AffineCurve, EFqSponge> {
+    p_eval: [Vec<Fr<G>>; 2],
+    p_comm: PolyComm<G>,
+    f_comm: PolyComm<G>,
+    fq_sponge: EFqSponge,
+    oracles: RandomOracles<Fr<G>>,
+    polys: Vec<(PolyComm<G>, Vec<Vec<Fr<G>>>)>,
+}
+
+// This function checks the points of the proofs to be valid, before the verification runs any
+// multi-scalar multiplication over them, the verifier indices being validated once when they
+// are assembled
+//     proofs: vector of Plonk proofs
+fn validate_batch<G: CommitmentCurve, S: PolynomialCommitment<Curve = G>>(
+    proofs: &Vec<(&Index<G, S>, &Vec<PolyComm<G>>, &ProverProof<G, S::Proof>)>,
+) -> Result<(), ProofError> {
+    for (index, _, proof) in proofs.iter() {
+        proof.validate(index)?;
+    }
+    Ok(())
+}
+
+// This function selects the longest commitment key of the batch to verify the opening proofs
+// against, the shorter ones have to be its prefixes
+//     proofs: vector of Plonk proofs
+//     RETURN: the longest commitment key and the segment sizes of the keys of the proofs
+fn batch_srs<'a, G: CommitmentCurve, S: PolynomialCommitment<Curve = G>>(
+    proofs: &Vec<(
+        &'a Index<G, S>,
+        &Vec<PolyComm<G>>,
+        &ProverProof<G, S::Proof>,
+    )>,
+) -> Result<(&'a S, Vec<usize>), ProofError> {
+    let srs = proofs
+        .iter()
+        .map(|(index, _, _)| index.srs.get_ref())
+        .max_by_key(|srs| srs.segment_size())
+        .unwrap();
+    let mut srs_lengths = vec![];
+    for (index, _, _) in proofs.iter() {
+        let index_srs = index.srs.get_ref();
+        if !std::ptr::eq(index_srs, srs) && !index_srs.is_prefix_of(srs) {
+            return Err(ProofError::IncompatibleSrs);
+        }
+        srs_lengths.push(index_srs.segment_size());
+    }
+    Ok((srs, srs_lengths))
+}
@@ -9,7 +9,9 @@ use algebra::PrimeField;
 use array_init::array_init;
 use commitment_dlog::{
     commitment::{CommitmentCurve, PolyComm},
-    srs::{SRSSpec, SRSValue},
+    scheme::PolynomialCommitment,
+    srs::{endos, SRSSpec, SRSValue, SRS},
+    validation::InvalidPoint,
     CommitmentField,
 };
 use ff_fft::{DensePolynomial, Radix2EvaluationDomain as D};
@@ -19,15 +21,12 @@ use plonk_circuits::constraints::{zk_w, ConstraintSystem};
 type Fr<G> = <G as AffineCurve>::ScalarField;
 type Fq<G> = <G as AffineCurve>::BaseField;
 
-pub struct Index<'a, G: CommitmentCurve>
-where
-    G::ScalarField: CommitmentField,
-{
+pub struct Index<'a, G: CommitmentCurve, S = SRS<G>> {
     // constraints system polynoms
     pub cs: ConstraintSystem<Fr<G>>,
 
     // polynomial commitment keys
-    pub srs: SRSValue<'a, G>,
+    pub srs: SRSValue<'a, S>,
 
     // maximal size of polynomial section
     pub max_poly_size: usize,
@@ -37,13 +36,16 @@ where
 
     // random oracle argument parameters
     pub fq_sponge_params: ArithmeticSpongeParams<Fq<G>>,
+
+    // endoscalar coefficient of the scalar challenges
+    pub endo_r: Fr<G>,
 }
 
-pub struct VerifierIndex<'a, G: CommitmentCurve> {
+pub struct VerifierIndex<'a, G: CommitmentCurve, S = SRS<G>> {
     pub domain: D<Fr<G>>,     // evaluation domain
     pub max_poly_size: usize, // maximal size of polynomial section
     pub max_quot_size: usize, // maximal size of the quotient polynomial according to the supported constraints
-    pub srs: SRSValue<'a, G>, // polynomial commitment keys
+    pub srs: SRSValue<'a, S>, // polynomial commitment keys
 
     // index polynomial commitments
     pub sigma_comm: [PolyComm<G>; 3], // permutation commitment array
@@ -70,18 +72,18 @@ pub struct VerifierIndex<'a, G: CommitmentCurve> {
     pub zkpm: DensePolynomial<Fr<G>>, // zero-knowledge polynomial
     pub w: Fr<G>,                     // root of unity for zero-knowledge
     pub endo: Fr<G>,                  // endoscalar coefficient
+    pub endo_r: Fr<G>,                // endoscalar coefficient of the scalar challenges
 
     // random oracle argument parameters
     pub fr_sponge_params: ArithmeticSpongeParams<Fr<G>>,
     pub fq_sponge_params: ArithmeticSpongeParams<Fq<G>>,
 }
 
-impl<'a, G: CommitmentCurve> Index<'a, G>
+impl<'a, G: CommitmentCurve, S: Clone + PolynomialCommitment<Curve = G>> Index<'a, G, S>
 where
     G::BaseField: PrimeField,
-    G::ScalarField: CommitmentField,
 {
-    pub fn verifier_index(&self) -> VerifierIndex<G> {
+    pub fn verifier_index(&self) -> VerifierIndex<G, S> {
         let srs = match &self.srs {
             SRSValue::Value(s) => SRSValue::Value(s.clone()),
             SRSValue::Ref(x) => SRSValue::Ref(x),
@@ -111,6 +113,7 @@ where
             fr_sponge_params: self.cs.fr_sponge_params.clone(),
             fq_sponge_params: self.fq_sponge_params.clone(),
             endo: self.cs.endo,
+            endo_r: self.endo_r,
             max_poly_size: self.max_poly_size,
             max_quot_size: self.max_quot_size,
             zkpm: self.cs.zkpm.clone(),
@@ -120,15 +123,14 @@ where
         }
     }
 
-    // this function compiles the index from constraints
-    pub fn create(
+    // this function compiles the index from constraints against a commitment key of any scheme
+    pub fn create_with_srs(
         mut cs: ConstraintSystem<Fr<G>>,
         fq_sponge_params: ArithmeticSpongeParams<Fq<G>>,
         endo_q: Fr<G>,
-        srs: SRSSpec<'a, G>,
+        srs: SRSValue<'a, S>,
     ) -> Self {
-        let srs = SRSValue::create(srs);
-        let max_poly_size = srs.get_ref().g.len();
+        let max_poly_size = srs.get_ref().segment_size();
         if cs.public > 0 {
             assert!(
                 max_poly_size >= cs.domain.d1.size as usize,
@@ -139,9 +141,57 @@ where
         Index {
             max_quot_size: PlonkSpongeConstants::SPONGE_BOX * (cs.domain.d1.size as usize - 1),
             fq_sponge_params,
+            endo_r: endos::<G>().1,
             max_poly_size,
             srs,
             cs,
         }
     }
 }
+
+impl<'a, G: CommitmentCurve> Index<'a, G>
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: CommitmentField,
+{
+    // this function compiles the index from constraints
+    pub fn create(
+        cs: ConstraintSystem<Fr<G>>,
+        fq_sponge_params: ArithmeticSpongeParams<Fq<G>>,
+        endo_q: Fr<G>,
+        srs: SRSSpec<'a, G>,
+    ) -> Self {
+        Self::create_with_srs(cs, fq_sponge_params, endo_q, SRSValue::create(srs))
+    }
+}
+
+impl<'a, G: CommitmentCurve, S> VerifierIndex<'a, G, S> {
+    // This function checks the commitments of the verifier index to be on the curve. The
+    // verifier does not check them on every verification: the callers assembling a verifier
+    // index from untrusted data run it once before verifying against the index, while those
+    // computed by Index::verifier_index are valid by construction
+    pub fn validate(&self) -> Result<(), InvalidPoint> {
+        for comm in self.sigma_comm.iter().chain(self.rcm_comm.iter()) {
+            comm.validate("verifier index commitment")?;
+        }
+        for comm in [
+            &self.ql_comm,
+            &self.qr_comm,
+            &self.qo_comm,
+            &self.qm_comm,
+            &self.qc_comm,
+            &self.psm_comm,
+            &self.add_comm,
+            &self.mul1_comm,
+            &self.mul2_comm,
+            &self.emul1_comm,
+            &self.emul2_comm,
+            &self.emul3_comm,
+        ]
+        .iter()
+        {
+            comm.validate("verifier index commitment")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+/*****************************************************************************************************************
+
+This source file tests the split verification of batched opening proofs into the succinct
+check and the deferred accumulator check
+
+*****************************************************************************************************************/
+
+mod common;
+
+use algebra::{AffineCurve, ProjectiveCurve, UniformRand};
+use commitment_dlog::{commitment::CommitmentCurve, srs::SRS};
+use common::{batch, open, EFqSponge};
+use groupmap::GroupMap;
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+#[test]
+fn accumulator_test() {
+    let rng = &mut rand::thread_rng();
+
+    let size = 1 << 6;
+    let srs = SRS::<Affine>::create(size);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let polys = [(size / 2, None), (size, None), (3 * size / 2, None)];
+
+    let openings = (0..5)
+        .map(|_| open(&srs, &group_map, &polys))
+        .collect::<Vec<_>>();
+
+    // the split verification agrees with the full one
+    assert!(srs.verify::<EFqSponge>(&group_map, &mut batch(&openings), rng));
+    let accumulators = srs
+        .succinct_verify::<EFqSponge>(&group_map, &mut batch(&openings), rng)
+        .expect("succinct check failed");
+    assert_eq!(accumulators.len(), openings.len());
+    assert!(srs.check_accumulators(&accumulators, rng));
+
+    // accumulators of several batches can be decided together
+    let more = (0..2)
+        .map(|_| open(&srs, &group_map, &polys))
+        .collect::<Vec<_>>();
+    let mut all = srs
+        .succinct_verify::<EFqSponge>(&group_map, &mut batch(&more), rng)
+        .expect("succinct check failed");
+    all.extend(accumulators.clone());
+    assert!(srs.check_accumulators(&all, rng));
+
+    // a wrong sg is only caught by the deferred check
+    let mut bad = accumulators.clone();
+    bad[2].sg = (bad[2].sg.into_projective()
+        + &Affine::prime_subgroup_generator().into_projective())
+        .into_affine();
+    assert!(!srs.check_accumulators(&bad, rng));
+
+    // a wrong opening is caught by the succinct check
+    let mut openings = openings;
+    openings[1].proof.z1 += &Fp::rand(rng);
+    assert!(srs
+        .succinct_verify::<EFqSponge>(&group_map, &mut batch(&openings), rng)
+        .is_none());
+}
@@ -0,0 +1,58 @@
+/*****************************************************************************************************************
+
+This source file tests that the failing proofs of a batch of opening proofs are
+pointed out with the check they fail
+
+*****************************************************************************************************************/
+
+mod common;
+
+use algebra::One;
+use commitment_dlog::{commitment::CommitmentCurve, srs::SRS};
+use common::{batch, open, EFqSponge};
+use groupmap::GroupMap;
+use mina_curves::pasta::{vesta::Affine, Fp};
+use oracle::rndoracle::FailedCheck;
+
+#[test]
+fn batch_failures_test() {
+    let rng = &mut rand::thread_rng();
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let size = 1 << 5;
+    let srs = SRS::<Affine>::create(size);
+    // an SRS sharing h with srs but not its generators g
+    let mut g = srs.g().to_vec();
+    g.reverse();
+    let forged = SRS::new(g, srs.h());
+
+    // a couple of polynomials opened against srs
+    let polys = [(size - 1, None), (size / 2, Some(size / 2 + 1))];
+    let mut openings = (0..5)
+        .map(|i| open(if i == 2 { &forged } else { &srs }, &group_map, &polys))
+        .collect::<Vec<_>>();
+    openings[1].proof.z1 += &Fp::one();
+    openings[4].proof.z2 += &Fp::one();
+
+    let batch = batch(&openings);
+    let lengths = vec![size; batch.len()];
+
+    assert!(!srs.verify::<EFqSponge>(&group_map, &mut batch.clone(), rng));
+    assert_eq!(
+        srs.verify_failures::<EFqSponge>(&group_map, &batch, &lengths, rng),
+        vec![
+            (1, FailedCheck::Ipa),
+            (2, FailedCheck::Sg),
+            (4, FailedCheck::Ipa)
+        ]
+    );
+
+    // the proofs which pass are the ones verifying on their own
+    assert!(srs.verify::<EFqSponge>(
+        &group_map,
+        &mut vec![batch[0].clone(), batch[3].clone()],
+        rng
+    ));
+    assert!(srs
+        .verify_failures::<EFqSponge>(&group_map, &batch[0..1].to_vec(), &lengths[0..1], rng)
+        .is_empty());
+}
@@ -0,0 +1,65 @@
+/*****************************************************************************************************************
+
+This source file tests the polynomial of the challenges of the opening proofs, its evaluations,
+the evaluations of its tails and its commitment against those of its coefficients
+
+*****************************************************************************************************************/
+
+use algebra::{One, UniformRand, Zero};
+use commitment_dlog::{
+    commitment::{b_poly_coefficients, ChallengePolynomial},
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+#[test]
+fn challenge_polynomial() {
+    let rng = &mut rand::thread_rng();
+    let k = 6;
+    let chals: Vec<Fp> = (0..k).map(|_| Fp::rand(rng)).collect();
+    let mut b = ChallengePolynomial::new(chals.clone());
+    let coefficients = b_poly_coefficients(&chals);
+    assert_eq!(b.size(), coefficients.len());
+
+    for (j, c) in coefficients.iter().enumerate() {
+        assert_eq!(b.coefficient(j), *c);
+    }
+    assert_eq!(b.coefficient(1 << k), Fp::zero());
+
+    let x = Fp::rand(rng);
+    let p = DensePolynomial::from_coefficients_slice(&coefficients);
+    assert_eq!(b.evaluate(x), p.evaluate(x));
+
+    // sum_{j >= start} b_j x^(j - start), at every start including past the end
+    for start in 0..=b.size() {
+        let (tail, _) = coefficients[start..]
+            .iter()
+            .fold((Fp::zero(), Fp::one()), |(acc, pow), c| {
+                (acc + &(pow * c), pow * &x)
+            });
+        assert_eq!(b.evaluate_tail(start, x), tail);
+    }
+    assert_eq!(b.evaluate_tail(0, Fp::zero()), Fp::one());
+
+    assert_eq!(b.coefficients(), &coefficients[..]);
+}
+
+#[test]
+fn challenge_polynomial_commitment() {
+    let rng = &mut rand::thread_rng();
+    let chals: Vec<Fp> = (0..5).map(|_| Fp::rand(rng)).collect();
+    let p = DensePolynomial::from_coefficients_vec(b_poly_coefficients(&chals));
+
+    // in a single segment, and in several ones over a shorter SRS
+    for depth in [1 << 5, 1 << 3].iter() {
+        let mut srs = SRS::<Affine>::create(*depth);
+        let mut b = ChallengePolynomial::new(chals.clone());
+        let comm = b.commit(&srs);
+        assert_eq!(comm.unshifted, srs.commit_non_hiding(&p, None).unshifted);
+
+        // the fixed-base tables give the same commitment
+        srs.add_fixed_base_table(4);
+        assert_eq!(b.commit(&srs).unshifted, comm.unshifted);
+    }
+}
@@ -0,0 +1,190 @@
+/*****************************************************************************************************************
+
+This source file tests the polynomial commitment schemes through their common interface,
+the inner product argument over the pasta curves and the KZG scheme over BLS12-381
+
+*****************************************************************************************************************/
+
+use algebra::{
+    bls12_381::{g1, Bls12_381, G1Affine},
+    AffineCurve, One, UniformRand, Zero,
+};
+use commitment_dlog::{
+    commitment::{CommitmentCurve, PolyComm},
+    kzg,
+    scheme::PolynomialCommitment,
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::vesta::{Affine, VestaParameters};
+use oracle::keccak::KeccakFqSponge;
+use oracle::poseidon::{ArithmeticSpongeParams, PlonkSpongeConstants as SC};
+use oracle::sponge::DefaultFqSponge;
+use oracle::utils::PolyUtils;
+use oracle::FqSponge;
+
+type Fr<G> = <G as AffineCurve>::ScalarField;
+type Fq<G> = <G as AffineCurve>::BaseField;
+
+// This function opens a batch of polynomials, with and without degree bounds and spanning
+// several segments, and verifies the opening along with a tampered one
+fn open_and_verify<
+    S: PolynomialCommitment,
+    EFqSponge: Clone + FqSponge<Fq<S::Curve>, S::Curve, Fr<S::Curve>>,
+>(
+    scheme: &S,
+    group_map: &<S::Curve as CommitmentCurve>::Map,
+    sponge: &dyn Fn() -> EFqSponge,
+) {
+    let rng = &mut rand::thread_rng();
+    let size = scheme.segment_size();
+
+    let polys = vec![
+        (DensePolynomial::<Fr<S::Curve>>::rand(size - 1, rng), None),
+        (
+            DensePolynomial::<Fr<S::Curve>>::rand(2 * size - 1, rng),
+            None,
+        ),
+        (
+            DensePolynomial::<Fr<S::Curve>>::rand(size + size / 2, rng),
+            Some(size + size / 2),
+        ),
+        (DensePolynomial::<Fr<S::Curve>>::zero(), Some(size / 2)),
+    ];
+    let x = vec![Fr::<S::Curve>::rand(rng), Fr::<S::Curve>::rand(rng)];
+    let polyscale = Fr::<S::Curve>::rand(rng);
+    let evalscale = Fr::<S::Curve>::rand(rng);
+
+    let comms: Vec<(PolyComm<S::Curve>, PolyComm<Fr<S::Curve>>)> = polys
+        .iter()
+        .map(|(p, bound)| scheme.commit(p, *bound, rng))
+        .collect();
+    let mut evals: Vec<Vec<Vec<Fr<S::Curve>>>> = polys
+        .iter()
+        .map(|(p, _)| x.iter().map(|xx| p.eval(*xx, size)).collect())
+        .collect();
+
+    let proof = scheme.open(
+        group_map,
+        polys
+            .iter()
+            .zip(comms.iter())
+            .map(|((p, bound), (_, w))| (p, *bound, w.clone()))
+            .collect(),
+        &x,
+        polyscale,
+        evalscale,
+        sponge(),
+        rng,
+    );
+
+    let verify = |evals: &Vec<Vec<Vec<Fr<S::Curve>>>>, rng: &mut rand::rngs::ThreadRng| {
+        scheme.verify(
+            group_map,
+            sponge(),
+            x.clone(),
+            polyscale,
+            evalscale,
+            polys
+                .iter()
+                .zip(comms.iter())
+                .zip(evals.iter())
+                .map(|(((_, bound), (c, _)), e)| (c, e.iter().collect(), *bound))
+                .collect(),
+            &proof,
+            rng,
+        )
+    };
+    assert!(verify(&evals, rng));
+
+    evals[2][1][0] += Fr::<S::Curve>::one();
+    assert!(!verify(&evals, rng));
+}
+
+#[test]
+fn ipa_scheme() {
+    let srs = SRS::<Affine>::create(1 << 5);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    open_and_verify(&srs, &group_map, &|| {
+        DefaultFqSponge::<VestaParameters, SC>::new(oracle::pasta::fq::params())
+    });
+}
+
+// the Keccak-256 sponge takes no parameters
+fn keccak_sponge() -> KeccakFqSponge<g1::Parameters> {
+    KeccakFqSponge::new(ArithmeticSpongeParams {
+        round_constants: vec![],
+        mds: vec![],
+    })
+}
+
+#[test]
+fn kzg_scheme() {
+    let rng = &mut rand::thread_rng();
+    let srs = kzg::SRS::<Bls12_381>::create(1 << 5, rng);
+    let group_map = <G1Affine as CommitmentCurve>::Map::setup();
+    open_and_verify(&srs, &group_map, &keccak_sponge);
+}
+
+#[test]
+fn kzg_batch_verification() {
+    let rng = &mut rand::thread_rng();
+    let srs = kzg::SRS::<Bls12_381>::create(1 << 4, rng);
+    let group_map = <G1Affine as CommitmentCurve>::Map::setup();
+    type F = <Bls12_381 as algebra::PairingEngine>::Fr;
+
+    let p1 = DensePolynomial::<F>::rand(15, rng);
+    let p2 = DensePolynomial::<F>::rand(40, rng);
+    let (c1, w1) = srs.commit(&p1, None, rng);
+    let (c2, w2) = srs.commit(&p2, None, rng);
+    let (x1, x2) = (vec![F::rand(rng)], vec![F::rand(rng), F::rand(rng)]);
+    let (e1, e2): (Vec<_>, Vec<_>) = (
+        x1.iter().map(|x| p1.eval(*x, 16)).collect(),
+        x2.iter().map(|x| p2.eval(*x, 16)).collect(),
+    );
+    let (s1, s2) = (F::rand(rng), F::rand(rng));
+
+    let open = |p, w, x, s, rng: &mut rand::rngs::ThreadRng| {
+        srs.open(
+            &group_map,
+            vec![(p, None, w)],
+            x,
+            s,
+            F::zero(),
+            keccak_sponge(),
+            rng,
+        )
+    };
+    let proof1 = open(&p1, w1, &x1, s1, rng);
+    let mut proof2 = open(&p2, w2, &x2, s2, rng);
+
+    let mut batch = |proof2: &kzg::KzgProof<Bls12_381>| {
+        srs.batch_verify(
+            &group_map,
+            &mut vec![
+                (
+                    keccak_sponge(),
+                    x1.clone(),
+                    s1,
+                    F::zero(),
+                    vec![(&c1, e1.iter().collect(), None)],
+                    &proof1,
+                ),
+                (
+                    keccak_sponge(),
+                    x2.clone(),
+                    s2,
+                    F::zero(),
+                    vec![(&c2, e2.iter().collect(), None)],
+                    proof2,
+                ),
+            ],
+            rng,
+        )
+    };
+    assert!(batch(&proof2));
+
+    proof2.quotients.swap(0, 1);
+    assert!(!batch(&proof2));
+}
@@ -0,0 +1,278 @@
+/*****************************************************************************************************************
+
+This source file implements the fixture of the tests of batched opening proofs: the opening
+of random polynomials against SRS, at the same points or at their own sets of points, and the
+batch of the openings as the verifiers take it
+
+*****************************************************************************************************************/
+
+// every test uses only some of the fixture
+#![allow(dead_code)]
+
+use algebra::UniformRand;
+use commitment_dlog::{
+    commitment::{CommitmentCurve, MultiPointOpeningProof, OpeningProof, PolyComm},
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use mina_curves::pasta::{
+    vesta::{Affine, VestaParameters},
+    Fp, Fq,
+};
+use oracle::poseidon::PlonkSpongeConstants as SC;
+use oracle::sponge::DefaultFqSponge;
+use oracle::utils::PolyUtils;
+use oracle::FqSponge;
+use rand_core::RngCore;
+
+pub type EFqSponge = DefaultFqSponge<VestaParameters, SC>;
+
+pub struct Opening {
+    pub x: Vec<Fp>,
+    pub polymask: Fp,
+    pub evalmask: Fp,
+    pub comm: Vec<(PolyComm<Affine>, Vec<Vec<Fp>>, Option<usize>)>,
+    pub proof: OpeningProof<Affine>,
+}
+
+// Random polynomials committed against SRS and evaluated at two random points, to be opened
+// with any sponge and randomness source
+pub struct Committed {
+    pub polys: Vec<(DensePolynomial<Fp>, Option<usize>)>,
+    pub blinders: Vec<PolyComm<Fp>>,
+    pub x: Vec<Fp>,
+    pub polymask: Fp,
+    pub evalmask: Fp,
+    pub comm: Vec<(PolyComm<Affine>, Vec<Vec<Fp>>, Option<usize>)>,
+}
+
+// This function commits to random polynomials and evaluates them at two random points
+//     srs: SRS to commit against
+//     polys: degrees and optional degree bounds of the polynomials
+//     RETURN: polynomials, commitments and evaluations of the polynomials
+pub fn commit(srs: &SRS<Affine>, polys: &[(usize, Option<usize>)]) -> Committed {
+    let rng = &mut rand::thread_rng();
+    let size = srs.g().len();
+
+    let polys = polys
+        .iter()
+        .map(|(degree, bound)| (DensePolynomial::<Fp>::rand(*degree, rng), *bound))
+        .collect::<Vec<_>>();
+    let x = (0..2).map(|_| Fp::rand(rng)).collect::<Vec<Fp>>();
+
+    let (comm, blinders) = polys
+        .iter()
+        .map(|(p, bound)| {
+            let (c, blinders) = srs.commit(p, *bound, rng);
+            (
+                (c, x.iter().map(|xx| p.eval(*xx, size)).collect(), *bound),
+                blinders,
+            )
+        })
+        .unzip();
+
+    Committed {
+        polys,
+        blinders,
+        x,
+        polymask: Fp::rand(rng),
+        evalmask: Fp::rand(rng),
+        comm,
+    }
+}
+
+impl Committed {
+    // This function opens the polynomials at their evaluation points against srs
+    //     srs: SRS the polynomials are committed against
+    //     group_map: group map of the curve
+    //     sponge: sponge of the opening proof
+    //     rng: randomness source of the blinders of the opening proof
+    //     RETURN: opening proof of the polynomials
+    pub fn open<S: Clone + FqSponge<Fq, Affine, Fp>>(
+        &self,
+        srs: &SRS<Affine>,
+        group_map: &<Affine as CommitmentCurve>::Map,
+        sponge: S,
+        rng: &mut dyn RngCore,
+    ) -> OpeningProof<Affine> {
+        srs.open::<S>(
+            group_map,
+            self.polys
+                .iter()
+                .zip(self.blinders.iter())
+                .map(|((p, bound), blinders)| (p, *bound, blinders.clone()))
+                .collect::<Vec<_>>(),
+            &self.x,
+            self.polymask,
+            self.evalmask,
+            sponge,
+            rng,
+        )
+    }
+}
+
+// This function opens random polynomials at two random points against srs
+//     srs: SRS to commit and open against
+//     group_map: group map of the curve
+//     polys: degrees and optional degree bounds of the polynomials
+//     RETURN: commitments, evaluations and opening proof of the polynomials
+pub fn open(
+    srs: &SRS<Affine>,
+    group_map: &<Affine as CommitmentCurve>::Map,
+    polys: &[(usize, Option<usize>)],
+) -> Opening {
+    let committed = commit(srs, polys);
+    let proof = committed.open(
+        srs,
+        group_map,
+        EFqSponge::new(oracle::pasta::fq::params()),
+        &mut rand::thread_rng(),
+    );
+    Opening {
+        x: committed.x,
+        polymask: committed.polymask,
+        evalmask: committed.evalmask,
+        comm: committed.comm,
+        proof,
+    }
+}
+
+// This function returns the batch of the openings, as the verifiers of SRS take it
+pub fn batch<'a>(
+    openings: &'a [Opening],
+) -> Vec<(
+    EFqSponge,
+    Vec<Fp>,
+    Fp,
+    Fp,
+    Vec<(&'a PolyComm<Affine>, Vec<&'a Vec<Fp>>, Option<usize>)>,
+    &'a OpeningProof<Affine>,
+)> {
+    openings
+        .iter()
+        .map(|o| {
+            (
+                EFqSponge::new(oracle::pasta::fq::params()),
+                o.x.clone(),
+                o.polymask,
+                o.evalmask,
+                o.comm
+                    .iter()
+                    .map(|(c, e, bound)| (c, e.iter().collect::<Vec<_>>(), *bound))
+                    .collect::<Vec<_>>(),
+                &o.proof,
+            )
+        })
+        .collect()
+}
+
+pub struct MultiPointOpening {
+    pub x: Vec<Fp>,
+    pub polyscale: Fp,
+    pub evalscale: Fp,
+    // commitments, evaluations at the points of the polynomials with the indices of the points
+    // in x, and degree bounds
+    pub comm: Vec<(PolyComm<Affine>, Vec<(usize, Vec<Fp>)>, Option<usize>)>,
+    pub proof: MultiPointOpeningProof<Affine>,
+}
+
+// This function opens random polynomials at their own sets of random points against srs
+//     srs: SRS to commit and open against
+//     group_map: group map of the curve
+//     points: number of evaluation points
+//     polys: degrees, optional degree bounds and indices of the evaluation points of the polynomials
+//     RETURN: commitments, evaluations and multi-point opening proof of the polynomials
+pub fn open_multi_point(
+    srs: &SRS<Affine>,
+    group_map: &<Affine as CommitmentCurve>::Map,
+    points: usize,
+    polys: &[(usize, Option<usize>, Vec<usize>)],
+) -> MultiPointOpening {
+    let rng = &mut rand::thread_rng();
+    let size = srs.g().len();
+
+    let polys = polys
+        .iter()
+        .map(|(degree, bound, points)| {
+            (
+                DensePolynomial::<Fp>::rand(*degree, rng),
+                *bound,
+                points.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let x = (0..points).map(|_| Fp::rand(rng)).collect::<Vec<Fp>>();
+    let polyscale = Fp::rand(rng);
+    let evalscale = Fp::rand(rng);
+
+    let comm = polys
+        .iter()
+        .map(|(p, bound, _)| srs.commit(p, *bound, rng))
+        .collect::<Vec<_>>();
+    let proof = srs.open_multi_point::<EFqSponge>(
+        group_map,
+        polys
+            .iter()
+            .zip(comm.iter())
+            .map(|((p, bound, points), (_, blinders))| {
+                (p, *bound, blinders.clone(), points.clone())
+            })
+            .collect(),
+        &x,
+        polyscale,
+        evalscale,
+        EFqSponge::new(oracle::pasta::fq::params()),
+        rng,
+    );
+
+    MultiPointOpening {
+        comm: polys
+            .iter()
+            .zip(comm.iter())
+            .map(|((p, bound, points), (c, _))| {
+                (
+                    c.clone(),
+                    points.iter().map(|j| (*j, p.eval(x[*j], size))).collect(),
+                    *bound,
+                )
+            })
+            .collect(),
+        x,
+        polyscale,
+        evalscale,
+        proof,
+    }
+}
+
+// This function returns the batch of the multi-point openings, as the verifiers of SRS take it
+pub fn multi_point_batch<'a>(
+    openings: &'a [MultiPointOpening],
+) -> Vec<(
+    EFqSponge,
+    Vec<Fp>,
+    Fp,
+    Fp,
+    Vec<(
+        &'a PolyComm<Affine>,
+        Vec<(usize, &'a Vec<Fp>)>,
+        Option<usize>,
+    )>,
+    &'a MultiPointOpeningProof<Affine>,
+)> {
+    openings
+        .iter()
+        .map(|o| {
+            (
+                EFqSponge::new(oracle::pasta::fq::params()),
+                o.x.clone(),
+                o.polyscale,
+                o.evalscale,
+                o.comm
+                    .iter()
+                    .map(|(c, e, bound)| (c, e.iter().map(|(j, v)| (*j, v)).collect(), *bound))
+                    .collect(),
+                &o.proof,
+            )
+        })
+        .collect()
+}
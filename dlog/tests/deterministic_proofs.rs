@@ -0,0 +1,166 @@
+/*****************************************************************************************************************
+
+This source file tests that the proofs created with a caller-supplied randomness source
+are reproducible byte for byte, for both the 3-wire and the 5-wire plonk protocols
+
+*****************************************************************************************************************/
+
+use algebra::{One, ToBytes, UniformRand, Zero};
+use array_init::array_init;
+use commitment_dlog::{
+    commitment::CommitmentCurve,
+    srs::{endos, SRSSpec, SRS},
+};
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    pallas::Affine as Other,
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::{
+    poseidon::{ArithmeticSpongeParams, PlonkSpongeConstants as SC, PlonkSpongeConstants5W},
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const N: usize = 16;
+
+fn to_bytes<T: ToBytes>(x: &T) -> Vec<u8> {
+    let mut bytes = vec![];
+    x.write(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn deterministic_proofs() {
+    use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
+    use plonk_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // l + r = o on every row
+    let gates = (0..N)
+        .map(|i| {
+            CircuitGate::<Fp>::create_generic(
+                GateWires::wires((i, i), (N + i, N + i), (2 * N + i, 2 * N + i)),
+                Fp::one(),
+                Fp::one(),
+                -Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let mut srs = SRS::create(N);
+    srs.add_lagrange_basis(cs.domain.d1);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let l: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let r: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let o: Vec<Fp> = l.iter().zip(r.iter()).map(|(l, r)| *l + r).collect();
+    let witness = [l, r, o].concat();
+    assert!(index.cs.verify(&witness));
+
+    type EFqSponge = DefaultFqSponge<VestaParameters, SC>;
+    type EFrSponge = DefaultFrSponge<Fp, SC>;
+    let prove = |seed: u64| {
+        ProverProof::create_with_rng::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &witness,
+            &index,
+            vec![],
+            &mut StdRng::seed_from_u64(seed),
+        )
+        .unwrap()
+    };
+    let (proof1, proof2, proof3) = (prove(1), prove(1), prove(2));
+    assert_eq!(to_bytes(&proof1), to_bytes(&proof2));
+    assert_ne!(to_bytes(&proof1), to_bytes(&proof3));
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    assert!(ProverProof::verify::<EFqSponge, EFrSponge, _>(
+        &group_map,
+        &vec![
+            (&verifier_index, &lgr_comms, &proof1),
+            (&verifier_index, &lgr_comms, &proof3)
+        ]
+    )
+    .is_ok());
+}
+
+#[test]
+fn deterministic_proofs_5_wires() {
+    use plonk_5_wires_circuits::{
+        constraints::ConstraintSystem, gate::CircuitGate, wires::Wire, wires::COLUMNS,
+    };
+    use plonk_5_wires_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // w0 + w1 = w2 on every row
+    let gates = (0..N)
+        .map(|row| {
+            CircuitGate::<Fp>::create_generic(
+                row,
+                array_init(|col| Wire { row, col }),
+                [Fp::one(), Fp::one(), -Fp::one(), Fp::zero(), Fp::zero()],
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp5::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let mut srs = SRS::create(N);
+    srs.add_lagrange_basis(cs.domain.d1);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq5::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let mut witness: [Vec<Fp>; COLUMNS] = array_init(|_| vec![Fp::zero(); N]);
+    for row in 0..N {
+        witness[0][row] = Fp::rand(rng);
+        witness[1][row] = Fp::rand(rng);
+        witness[2][row] = witness[0][row] + &witness[1][row];
+    }
+
+    type EFqSponge = DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>;
+    type EFrSponge = DefaultFrSponge<Fp, PlonkSpongeConstants5W>;
+    let prove = |seed: u64| {
+        ProverProof::create_with_rng::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &witness,
+            &index,
+            vec![],
+            &mut StdRng::seed_from_u64(seed),
+        )
+        .unwrap()
+    };
+    let (proof1, proof2, proof3) = (prove(1), prove(1), prove(2));
+    assert_eq!(to_bytes(&proof1), to_bytes(&proof2));
+    assert_ne!(to_bytes(&proof1), to_bytes(&proof3));
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    assert!(ProverProof::verify::<EFqSponge, EFrSponge, _>(
+        &group_map,
+        &vec![
+            (&verifier_index, &lgr_comms, &proof1),
+            (&verifier_index, &lgr_comms, &proof3)
+        ]
+    )
+    .is_ok());
+}
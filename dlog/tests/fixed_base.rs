@@ -0,0 +1,147 @@
+/*****************************************************************************************************************
+
+This source file tests the commitments and opening proofs computed with the fixed-base
+tables of the generators of SRS
+
+*****************************************************************************************************************/
+
+use algebra::UniformRand;
+use commitment_dlog::{
+    commitment::{CommitmentCurve, PolyComm},
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::poseidon::PlonkSpongeConstants as SC;
+use oracle::sponge::DefaultFqSponge;
+use oracle::utils::PolyUtils;
+use oracle::FqSponge;
+use std::io::ErrorKind;
+
+type EFqSponge = DefaultFqSponge<VestaParameters, SC>;
+
+#[test]
+fn fixed_base_commitment_test() {
+    let rng = &mut rand::thread_rng();
+    let size = 1 << 5;
+    let srs = SRS::<Affine>::create(size);
+
+    let polys = vec![
+        (DensePolynomial::<Fp>::rand(size / 2, rng), None),
+        (DensePolynomial::<Fp>::rand(3 * size - 1, rng), None),
+        (DensePolynomial::<Fp>::rand(size - 3, rng), Some(size - 2)),
+        (DensePolynomial::<Fp>::rand(size + 5, rng), Some(size + 7)),
+    ];
+
+    for window in [1, 4, 8].iter() {
+        let mut table = srs.clone();
+        table.add_fixed_base_table(*window);
+
+        for (p, bound) in polys.iter() {
+            let expected = srs.commit_non_hiding(p, *bound);
+            let comm = table.commit_non_hiding(p, *bound);
+            assert_eq!(comm.unshifted, expected.unshifted);
+            assert_eq!(comm.shifted, expected.shifted);
+        }
+
+        let x = Fp::rand(rng);
+        assert_eq!(table.h_mul(x), srs.h_mul(x));
+        let scalars = (0..size).map(|_| Fp::rand(rng)).collect::<Vec<_>>();
+        assert_eq!(table.g_msm(3, &scalars), srs.g_msm(3, &scalars));
+    }
+}
+
+#[test]
+fn fixed_base_opening_test() {
+    let rng = &mut rand::thread_rng();
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let size = 1 << 5;
+    let mut srs = SRS::<Affine>::create(size);
+    srs.add_fixed_base_table(4);
+
+    let polys = vec![
+        (DensePolynomial::<Fp>::rand(size - 1, rng), None),
+        (DensePolynomial::<Fp>::rand(2 * size - 1, rng), None),
+        (
+            DensePolynomial::<Fp>::rand(size / 2, rng),
+            Some(size / 2 + 1),
+        ),
+    ];
+    let x = vec![Fp::rand(rng), Fp::rand(rng)];
+    let polymask = Fp::rand(rng);
+    let evalmask = Fp::rand(rng);
+
+    let comms: Vec<(PolyComm<Affine>, PolyComm<Fp>)> = polys
+        .iter()
+        .map(|(p, bound)| srs.commit(p, *bound, rng))
+        .collect();
+    let evals: Vec<Vec<Vec<Fp>>> = polys
+        .iter()
+        .map(|(p, _)| x.iter().map(|xx| p.eval(*xx, size)).collect())
+        .collect();
+
+    let proof = srs.open::<EFqSponge>(
+        &group_map,
+        polys
+            .iter()
+            .zip(comms.iter())
+            .map(|((p, bound), (_, w))| (p, *bound, w.clone()))
+            .collect(),
+        &x,
+        polymask,
+        evalmask,
+        EFqSponge::new(oracle::pasta::fq::params()),
+        rng,
+    );
+
+    // the proof verifies against the SRS without the tables as well
+    let verifier = SRS::<Affine>::create(size);
+    let mut batch = vec![(
+        EFqSponge::new(oracle::pasta::fq::params()),
+        x.clone(),
+        polymask,
+        evalmask,
+        polys
+            .iter()
+            .zip(comms.iter())
+            .zip(evals.iter())
+            .map(|(((_, bound), (c, _)), e)| (c, e.iter().collect(), *bound))
+            .collect(),
+        &proof,
+    )];
+    assert!(verifier.verify::<EFqSponge>(&group_map, &mut batch, rng));
+}
+
+#[test]
+fn fixed_base_table_serialization() {
+    let mut srs = SRS::<Affine>::create(1 << 4);
+    srs.add_fixed_base_table(5);
+
+    let mut bytes = vec![];
+    srs.write_fixed_base_table(&mut bytes).unwrap();
+
+    let rng = &mut rand::thread_rng();
+    let mut other = SRS::<Affine>::create(1 << 4);
+    assert!(other.write_fixed_base_table(&mut vec![]).is_err());
+    other.read_fixed_base_table(&bytes[..], rng).unwrap();
+    let table = other.fixed_base.clone().unwrap();
+    let expected = srs.fixed_base.unwrap();
+    assert_eq!(table.window, expected.window);
+    assert_eq!(table.multiples, expected.multiples);
+
+    // the table is bound to the length of SRS it was computed for
+    let mut shorter = SRS::<Affine>::create(1 << 3);
+    assert!(shorter.read_fixed_base_table(&bytes[..], rng).is_err());
+
+    // and to the multiples of its generators, past the first ones
+    let mut forged = table.clone();
+    forged.multiples[3][2] = forged.multiples[3][1];
+    let mut bytes = vec![];
+    forged.write(&mut bytes).unwrap();
+    let err = other.read_fixed_base_table(&bytes[..], rng).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
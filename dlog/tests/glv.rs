@@ -0,0 +1,119 @@
+/*****************************************************************************************************************
+
+This source file tests the scalar decomposition and the multi-scalar multiplication using the
+endomorphism of the pasta curves
+
+*****************************************************************************************************************/
+
+use algebra::{
+    AffineCurve, BigInteger, One, PrimeField, ProjectiveCurve, UniformRand, VariableBaseMSM, Zero,
+};
+use commitment_dlog::{commitment::CommitmentCurve, glv::Glv, srs::endos};
+use mina_curves::pasta::{
+    pallas::{Affine as Pallas, PallasParameters},
+    vesta::{Affine as Vesta, VestaParameters},
+    Fp, Fq,
+};
+
+// the decomposition of random and edge scalars recomposes them with half-width scalars
+#[test]
+fn glv_decomposition_test() {
+    let rng = &mut rand::thread_rng();
+    let (endo_q, endo_r) = endos::<Vesta>();
+    let glv = Glv::<VestaParameters>::new(endo_q, endo_r).unwrap();
+
+    let mut scalars = vec![Fp::zero(), Fp::one(), -Fp::one(), endo_r, -endo_r];
+    scalars.extend((0..1000).map(|_| Fp::rand(rng)));
+    for k in scalars.iter() {
+        let ((neg1, k1), (neg2, k2)) = glv.decompose(*k);
+        assert!(k1.num_bits() <= 130 && k2.num_bits() <= 130);
+        let k1 = if neg1 {
+            -Fp::from_repr(k1)
+        } else {
+            Fp::from_repr(k1)
+        };
+        let k2 = if neg2 {
+            -Fp::from_repr(k2)
+        } else {
+            Fp::from_repr(k2)
+        };
+        assert_eq!(k1 + &(k2 * &endo_r), *k);
+    }
+}
+
+fn msm_test<G: CommitmentCurve>(sizes: &[usize])
+where
+    G::BaseField: PrimeField,
+{
+    let rng = &mut rand::thread_rng();
+    let (endo_q, endo_r) = endos::<G>();
+    let glv = G::glv(endo_q, endo_r);
+
+    for n in sizes.iter() {
+        let mut bases = (0..*n)
+            .map(|_| {
+                G::prime_subgroup_generator()
+                    .mul(G::ScalarField::rand(rng))
+                    .into_affine()
+            })
+            .collect::<Vec<_>>();
+        let mut scalars = (0..*n)
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        // identity bases and zero scalars are handled
+        if *n > 2 {
+            bases[0] = G::zero();
+            scalars[1] = G::ScalarField::zero();
+            scalars[2] = -G::ScalarField::one();
+        }
+
+        let expected = VariableBaseMSM::multi_scalar_mul(
+            &bases,
+            &scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>(),
+        );
+        assert_eq!(G::msm(&glv, &bases, &scalars), expected);
+    }
+}
+
+#[test]
+fn glv_msm_test() {
+    msm_test::<Vesta>(&[0, 1, 2, 31, 32, 100, 1 << 10]);
+    msm_test::<Pallas>(&[1, 5, 64, 1000]);
+}
+
+// a pair which is not the endomorphism and its eigenvalue is refused, the multi-scalar
+// multiplication falling back to the one of zexe
+#[test]
+fn glv_wrong_eigenvalue_test() {
+    let rng = &mut rand::thread_rng();
+    let (endo_q, endo_r) = endos::<Vesta>();
+    assert!(Glv::<VestaParameters>::new(endo_q, endo_r * &endo_r).is_none());
+
+    let glv = Vesta::glv(endo_q, -endo_r);
+    assert!(glv.is_none());
+    let bases = (0..10)
+        .map(|_| {
+            Vesta::prime_subgroup_generator()
+                .mul(Fp::rand(rng))
+                .into_affine()
+        })
+        .collect::<Vec<_>>();
+    let scalars = (0..10).map(|_| Fp::rand(rng)).collect::<Vec<_>>();
+    let expected = VariableBaseMSM::multi_scalar_mul(
+        &bases,
+        &scalars.iter().map(|s| s.into_repr()).collect::<Vec<_>>(),
+    );
+    assert_eq!(Vesta::msm(&glv, &bases, &scalars), expected);
+}
+
+#[test]
+fn glv_pallas_decomposition_test() {
+    let rng = &mut rand::thread_rng();
+    let (endo_q, endo_r) = endos::<Pallas>();
+    let glv = Glv::<PallasParameters>::new(endo_q, endo_r).unwrap();
+    for _ in 0..100 {
+        let k = Fq::rand(rng);
+        let ((_, k1), (_, k2)) = glv.decompose(k);
+        assert!(k1.num_bits() <= 130 && k2.num_bits() <= 130);
+    }
+}
@@ -0,0 +1,56 @@
+/*****************************************************************************************************************
+
+This source file tests batch verification of opening proofs made against
+SRS instances of different lengths
+
+*****************************************************************************************************************/
+
+mod common;
+
+use commitment_dlog::{commitment::CommitmentCurve, srs::SRS};
+use common::{batch, open, EFqSponge};
+use groupmap::GroupMap;
+use mina_curves::pasta::vesta::Affine;
+
+#[test]
+fn heterogeneous_srs_test() {
+    let rng = &mut rand::thread_rng();
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    // SRS is deterministic, so the shorter instances are prefixes of the longest one,
+    // including lengths which are not powers of two
+    let lengths = [16, 20, 64];
+    let srs = lengths
+        .iter()
+        .map(|n| SRS::<Affine>::create(*n))
+        .collect::<Vec<_>>();
+    let longest = &srs[2];
+
+    // a few polynomials, spanning several segments and degree bounded, opened against every SRS
+    let openings = srs
+        .iter()
+        .map(|srs| {
+            let size = srs.g().len();
+            let polys = [
+                (size / 2, None),
+                (2 * size + 1, None),
+                (size - 2, Some(size - 1)),
+            ];
+            open(srs, &group_map, &polys)
+        })
+        .collect::<Vec<_>>();
+
+    assert!(longest.verify_prefixes::<EFqSponge>(&group_map, &mut batch(&openings), &lengths, rng));
+
+    // verifying the proofs against the wrong lengths fails
+    assert!(!longest.verify::<EFqSponge>(&group_map, &mut batch(&openings), rng));
+    assert!(!longest.verify_prefixes::<EFqSponge>(
+        &group_map,
+        &mut batch(&openings),
+        &[20, 16, 64],
+        rng
+    ));
+
+    // lengths exceeding the SRS verified against are rejected
+    assert!(!srs[1].verify_prefixes::<EFqSponge>(&group_map, &mut batch(&openings), &lengths, rng));
+}
@@ -0,0 +1,117 @@
+/*****************************************************************************************************************
+
+This source file tests that hiding commitments blind every segment, including the
+segments committing to zero, and that such commitments can be opened and verified
+
+*****************************************************************************************************************/
+
+use algebra::{UniformRand, Zero};
+use commitment_dlog::{
+    commitment::{CommitmentCurve, PolyComm},
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::poseidon::PlonkSpongeConstants as SC;
+use oracle::sponge::DefaultFqSponge;
+use oracle::utils::PolyUtils;
+use oracle::FqSponge;
+
+#[test]
+fn zero_polynomial_commitment_is_hiding() {
+    let rng = &mut rand::thread_rng();
+    let size = 1 << 5;
+    let srs = SRS::<Affine>::create(size);
+    let zero = DensePolynomial::<Fp>::zero();
+
+    // the non-hiding commitment of the zero polynomial is empty
+    assert!(srs.commit_non_hiding(&zero, None).unshifted.is_empty());
+
+    let (c1, w1) = srs.commit(&zero, None, rng);
+    let (c2, _) = srs.commit(&zero, None, rng);
+    assert_eq!(c1.unshifted.len(), 1);
+    assert_eq!(w1.unshifted.len(), 1);
+    assert!(!c1.unshifted[0].is_zero());
+    assert_ne!(c1.unshifted[0], c2.unshifted[0]);
+
+    // the shifted part of a bounded commitment is blinded as well
+    let (c, w) = srs.commit(&zero, Some(size / 2), rng);
+    assert!(!c.shifted.unwrap().is_zero());
+    assert!(!w.shifted.unwrap().is_zero());
+
+    // so is the segment of a polynomial without coefficients past the first segment
+    let mut a = DensePolynomial::<Fp>::rand(size / 2, rng);
+    a.coeffs.resize(2 * size, Fp::zero());
+    let (c, _) = srs.commit(&a, None, rng);
+    assert_eq!(c.unshifted.len(), 2);
+    assert!(!c.unshifted[1].is_zero());
+}
+
+#[test]
+fn zero_polynomial_opening() {
+    let rng = &mut rand::thread_rng();
+    let size = 1 << 5;
+    let srs = SRS::<Affine>::create(size);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let sponge = DefaultFqSponge::<VestaParameters, SC>::new(oracle::pasta::fq::params());
+
+    let mut padded = DensePolynomial::<Fp>::rand(size / 2, rng);
+    padded.coeffs.resize(2 * size, Fp::zero());
+    // the last two bounded polynomials have no coefficients in their shifted segment
+    let short = DensePolynomial::<Fp>::rand(size - 1, rng);
+    let full = DensePolynomial::<Fp>::rand(2 * size - 1, rng);
+    let polys = vec![
+        (DensePolynomial::<Fp>::zero(), None),
+        (DensePolynomial::<Fp>::zero(), Some(size / 2)),
+        (short, Some(size + size / 2)),
+        (full, Some(2 * size)),
+        (padded, None),
+    ];
+
+    let x = vec![Fp::rand(rng), Fp::rand(rng)];
+    let polymask = Fp::rand(rng);
+    let evalmask = Fp::rand(rng);
+
+    let comms: Vec<(PolyComm<Affine>, PolyComm<Fp>)> = polys
+        .iter()
+        .map(|(p, bound)| srs.commit(p, *bound, rng))
+        .collect();
+    let evals: Vec<Vec<Vec<Fp>>> = polys
+        .iter()
+        .map(|(p, _)| x.iter().map(|xx| p.eval(*xx, size)).collect())
+        .collect();
+    assert_eq!(evals[0][0], vec![Fp::zero()]);
+
+    let proof = srs.open::<DefaultFqSponge<VestaParameters, SC>>(
+        &group_map,
+        polys
+            .iter()
+            .zip(comms.iter())
+            .map(|((p, bound), (_, w))| (p, *bound, w.clone()))
+            .collect(),
+        &x,
+        polymask,
+        evalmask,
+        sponge.clone(),
+        rng,
+    );
+
+    let mut batch = vec![(
+        sponge,
+        x.clone(),
+        polymask,
+        evalmask,
+        polys
+            .iter()
+            .zip(comms.iter())
+            .zip(evals.iter())
+            .map(|(((_, bound), (c, _)), e)| (c, e.iter().collect(), *bound))
+            .collect(),
+        &proof,
+    )];
+    assert!(srs.verify::<DefaultFqSponge<VestaParameters, SC>>(&group_map, &mut batch, rng));
+}
@@ -0,0 +1,211 @@
+/*****************************************************************************************************************
+
+This source file tests the simulator of the opening proofs: the simulated proofs verify against
+the programmed sponge, and the opening proofs of the prover are distributed as the simulated ones,
+which catches the regressions of the blinding of the opening proofs and of the commitments
+
+The distributions are compared with the two-sample chi-square statistic on the low bits of the
+scalars and of the coordinates, the threshold being exceeded by chance with probability < 1e-6
+
+*****************************************************************************************************************/
+
+mod common;
+
+use algebra::{PrimeField, Zero};
+use commitment_dlog::{
+    commitment::{CommitmentCurve, OpeningProof, PolyComm},
+    simulator::{OpeningChallenges, ProgrammedSponge},
+    srs::SRS,
+};
+use common::{commit, Committed};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::{vesta::Affine, Fp, Fq};
+use rand_core::{Error, RngCore};
+
+const SIZE: usize = 1 << 4;
+const ROUNDS: usize = 4;
+const SAMPLES: usize = 1 << 10;
+const BUCKETS: usize = 16;
+const THRESHOLD: f64 = 60.0;
+
+// A broken randomness source, with which the prover blinds nothing
+struct ZeroRng;
+
+impl RngCore for ZeroRng {
+    fn next_u32(&mut self) -> u32 {
+        0
+    }
+    fn next_u64(&mut self) -> u64 {
+        0
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.iter_mut().for_each(|b| *b = 0)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// An opening of a fixed polynomial commitment at fixed points with fixed challenges
+struct Opening {
+    srs: SRS<Affine>,
+    group_map: <Affine as CommitmentCurve>::Map,
+    committed: Committed,
+    challenges: OpeningChallenges<Affine>,
+}
+
+impl Opening {
+    fn create() -> Self {
+        let srs = SRS::<Affine>::create(SIZE);
+        let committed = commit(&srs, &[(SIZE - 1, None)]);
+        Opening {
+            srs,
+            group_map: <Affine as CommitmentCurve>::Map::setup(),
+            committed,
+            challenges: OpeningChallenges::rand(ROUNDS, &mut rand::thread_rng()),
+        }
+    }
+
+    fn comm(&self) -> Vec<(&PolyComm<Affine>, Vec<&Vec<Fp>>, Option<usize>)> {
+        self.committed
+            .comm
+            .iter()
+            .map(|(c, e, bound)| (c, e.iter().collect(), *bound))
+            .collect()
+    }
+
+    fn open(&self, rng: &mut dyn RngCore) -> OpeningProof<Affine> {
+        self.committed
+            .open(&self.srs, &self.group_map, self.challenges.sponge(), rng)
+    }
+
+    fn simulate(&self, rng: &mut dyn RngCore) -> OpeningProof<Affine> {
+        self.srs.simulate_opening(
+            &self.group_map,
+            &self.committed.x,
+            self.committed.polymask,
+            self.committed.evalmask,
+            &self.comm(),
+            &self.challenges,
+            rng,
+        )
+    }
+
+    fn verify(&self, proof: &OpeningProof<Affine>) -> bool {
+        let mut batch = vec![(
+            self.challenges.sponge(),
+            self.committed.x.clone(),
+            self.committed.polymask,
+            self.committed.evalmask,
+            self.comm(),
+            proof,
+        )];
+        self.srs.verify::<ProgrammedSponge<Fq, Fp>>(
+            &self.group_map,
+            &mut batch,
+            &mut rand::thread_rng(),
+        )
+    }
+}
+
+fn bucket<F: PrimeField>(x: F) -> usize {
+    (x.into_repr().as_ref()[0] as usize) % BUCKETS
+}
+
+// This function returns the histograms of the low bits of z1, z2 and of the x coordinates of
+// delta and of the first L
+fn histograms(proofs: &[OpeningProof<Affine>]) -> Vec<Vec<usize>> {
+    let mut hists = vec![vec![0; BUCKETS]; 4];
+    for proof in proofs.iter() {
+        hists[0][bucket(proof.z1)] += 1;
+        hists[1][bucket(proof.z2)] += 1;
+        hists[2][bucket(proof.delta.x)] += 1;
+        hists[3][bucket(proof.lr[0].0.x)] += 1;
+    }
+    hists
+}
+
+// This function computes the two-sample chi-square statistic of histograms of equal sample sizes
+fn chi_square(a: &[usize], b: &[usize]) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .filter(|(a, b)| *a + *b > 0)
+        .map(|(a, b)| {
+            let d = *a as f64 - *b as f64;
+            d * d / (*a + *b) as f64
+        })
+        .sum()
+}
+
+fn indistinguishable(a: &[OpeningProof<Affine>], b: &[OpeningProof<Affine>]) -> bool {
+    histograms(a)
+        .iter()
+        .zip(histograms(b).iter())
+        .all(|(a, b)| chi_square(a, b) < THRESHOLD)
+}
+
+#[test]
+fn simulated_opening_verifies() {
+    let rng = &mut rand::thread_rng();
+    let opening = Opening::create();
+
+    let simulated = opening.simulate(rng);
+    assert_eq!(simulated.lr.len(), ROUNDS);
+    assert!(opening.verify(&simulated));
+
+    // the prover answering the programmed challenges is verified as well
+    let real = opening.open(rng);
+    assert_eq!(real.lr.len(), ROUNDS);
+    assert_eq!(real.sg, simulated.sg);
+    assert!(opening.verify(&real));
+
+    // the simulated proof does not verify against other challenges
+    let other = Opening {
+        challenges: OpeningChallenges::rand(ROUNDS, rng),
+        ..opening
+    };
+    assert!(!other.verify(&simulated));
+}
+
+#[test]
+fn opening_proof_is_simulatable() {
+    let rng = &mut rand::thread_rng();
+    let opening = Opening::create();
+
+    let real: Vec<_> = (0..SAMPLES).map(|_| opening.open(rng)).collect();
+    let simulated: Vec<_> = (0..SAMPLES).map(|_| opening.simulate(rng)).collect();
+    assert!(indistinguishable(&real, &simulated));
+
+    // the opening proofs of a prover whose nonces are not random are told apart
+    let unblinded: Vec<_> = (0..SAMPLES).map(|_| opening.open(&mut ZeroRng)).collect();
+    assert!(opening.verify(&unblinded[0]));
+    assert!(!indistinguishable(&unblinded, &simulated));
+}
+
+#[test]
+fn zero_polynomial_commitment_is_simulatable() {
+    let rng = &mut rand::thread_rng();
+    let srs = SRS::<Affine>::create(SIZE);
+    let zero = DensePolynomial::<Fp>::zero();
+    let p = DensePolynomial::<Fp>::rand(SIZE - 1, rng);
+
+    // the identity, which a commitment to zero left unblinded would be, is a bucket of its own
+    let histogram = |p: &DensePolynomial<Fp>, rng: &mut dyn RngCore| {
+        let mut hist = vec![0; BUCKETS + 1];
+        for _ in 0..SAMPLES {
+            let (c, _) = srs.commit(p, None, rng);
+            let c = c.unshifted[0];
+            hist[if c.is_zero() { BUCKETS } else { bucket(c.x) }] += 1;
+        }
+        hist
+    };
+    let zero_hist = histogram(&zero, rng);
+    assert_eq!(zero_hist[BUCKETS], 0);
+    assert!(chi_square(&zero_hist, &histogram(&p, rng)) < THRESHOLD);
+
+    // the non-blinded commitments of zero are told apart
+    let unblinded = histogram(&zero, &mut ZeroRng);
+    assert!(chi_square(&unblinded, &histogram(&p, rng)) > THRESHOLD);
+}
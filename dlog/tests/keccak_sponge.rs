@@ -0,0 +1,136 @@
+/*****************************************************************************************************************
+
+This source file tests the Keccak-256 sponges as those of the 3-wire and the 5-wire plonk
+protocols: their proofs verify with the Keccak-256 sponges and not with the Poseidon ones
+
+*****************************************************************************************************************/
+
+use algebra::{One, UniformRand, Zero};
+use array_init::array_init;
+use commitment_dlog::{
+    commitment::CommitmentCurve,
+    srs::{endos, SRSSpec, SRS},
+};
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    pallas::Affine as Other,
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::{
+    keccak::{KeccakFqSponge, KeccakFrSponge},
+    poseidon::{ArithmeticSpongeParams, PlonkSpongeConstants as SC, PlonkSpongeConstants5W},
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+
+const N: usize = 16;
+
+type EFqSponge = KeccakFqSponge<VestaParameters>;
+type EFrSponge = KeccakFrSponge<Fp>;
+
+#[test]
+fn keccak_sponge() {
+    use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
+    use plonk_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // l + r = o on every row
+    let gates = (0..N)
+        .map(|i| {
+            CircuitGate::<Fp>::create_generic(
+                GateWires::wires((i, i), (N + i, N + i), (2 * N + i, 2 * N + i)),
+                Fp::one(),
+                Fp::one(),
+                -Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let srs = SRS::create(N);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let l: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let r: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let o: Vec<Fp> = l.iter().zip(r.iter()).map(|(l, r)| *l + r).collect();
+    let witness = [l, r, o].concat();
+
+    let proof =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    let batch = vec![(&verifier_index, &lgr_comms, &proof)];
+    assert!(ProverProof::verify::<EFqSponge, EFrSponge, _>(&group_map, &batch).is_ok());
+    assert!(
+        ProverProof::verify::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>, _>(
+            &group_map, &batch
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn keccak_sponge_5_wires() {
+    use plonk_5_wires_circuits::{
+        constraints::ConstraintSystem, gate::CircuitGate, wires::Wire, wires::COLUMNS,
+    };
+    use plonk_5_wires_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // w0 + w1 = w2 on every row
+    let gates = (0..N)
+        .map(|row| {
+            CircuitGate::<Fp>::create_generic(
+                row,
+                array_init(|col| Wire { row, col }),
+                [Fp::one(), Fp::one(), -Fp::one(), Fp::zero(), Fp::zero()],
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp5::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let srs = SRS::create(N);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq5::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let mut witness: [Vec<Fp>; COLUMNS] = array_init(|_| vec![Fp::zero(); N]);
+    for row in 0..N {
+        witness[0][row] = Fp::rand(rng);
+        witness[1][row] = Fp::rand(rng);
+        witness[2][row] = witness[0][row] + &witness[1][row];
+    }
+
+    let proof =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    let batch = vec![(&verifier_index, &lgr_comms, &proof)];
+    assert!(ProverProof::verify::<EFqSponge, EFrSponge, _>(&group_map, &batch).is_ok());
+    assert!(ProverProof::verify::<
+        DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
+        DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+        _,
+    >(&group_map, &batch)
+    .is_err());
+}
@@ -0,0 +1,143 @@
+/*****************************************************************************************************************
+
+This source file tests the 3-wire and the 5-wire plonk protocols over the KZG polynomial commitment
+scheme on BLS12-381, with the Keccak-256 sponges, which need no parameters of the curve: their
+proofs verify, and do not once an opening quotient of the KZG proof is swapped with another
+
+*****************************************************************************************************************/
+
+use algebra::{
+    bls12_381::{g1, Bls12_381, Fq, Fr, G1Affine},
+    One, UniformRand, Zero,
+};
+use array_init::array_init;
+use commitment_dlog::{commitment::CommitmentCurve, kzg, srs::SRSValue};
+use groupmap::GroupMap;
+use oracle::{
+    keccak::{KeccakFqSponge, KeccakFrSponge},
+    poseidon::{PlonkSpongeConstants, PlonkSpongeConstants5W},
+    poseidon_params::{params, Instance},
+    sponge::endo_coefficient,
+};
+
+const N: usize = 16;
+
+type EFqSponge = KeccakFqSponge<g1::Parameters>;
+type EFrSponge = KeccakFrSponge<Fr>;
+
+#[test]
+fn kzg_plonk() {
+    use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
+    use plonk_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // l + r = o on every row
+    let gates = (0..N)
+        .map(|i| {
+            CircuitGate::<Fr>::create_generic(
+                GateWires::wires((i, i), (N + i, N + i), (2 * N + i, 2 * N + i)),
+                Fr::one(),
+                Fr::one(),
+                -Fr::one(),
+                Fr::zero(),
+                Fr::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fr>::create(
+        gates,
+        params::<Fr, PlonkSpongeConstants>("Bls12_381_r", Instance::Legacy),
+        0,
+    )
+    .unwrap();
+    let rng = &mut rand::thread_rng();
+    let srs = kzg::SRS::<Bls12_381>::create(N, rng);
+    let index = Index::<G1Affine, _>::create_with_srs(
+        cs,
+        params::<Fq, PlonkSpongeConstants>("Bls12_381_q", Instance::Legacy),
+        endo_coefficient(),
+        SRSValue::Ref(&srs),
+    );
+    let group_map = <G1Affine as CommitmentCurve>::Map::setup();
+
+    let l: Vec<Fr> = (0..N).map(|_| Fr::rand(rng)).collect();
+    let r: Vec<Fr> = (0..N).map(|_| Fr::rand(rng)).collect();
+    let o: Vec<Fr> = l.iter().zip(r.iter()).map(|(l, r)| *l + r).collect();
+    let witness = [l, r, o].concat();
+
+    let mut proof =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    let verify = |proof: &ProverProof<G1Affine, kzg::KzgProof<Bls12_381>>| {
+        ProverProof::verify::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &vec![(&verifier_index, &lgr_comms, proof)],
+        )
+    };
+    assert!(verify(&proof).is_ok());
+
+    proof.proof.quotients.swap(0, 1);
+    assert!(verify(&proof).is_err());
+}
+
+#[test]
+fn kzg_plonk_5_wires() {
+    use plonk_5_wires_circuits::{
+        constraints::ConstraintSystem, gate::CircuitGate, wires::Wire, wires::COLUMNS,
+    };
+    use plonk_5_wires_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // w0 + w1 = w2 on every row
+    let gates = (0..N)
+        .map(|row| {
+            CircuitGate::<Fr>::create_generic(
+                row,
+                array_init(|col| Wire { row, col }),
+                [Fr::one(), Fr::one(), -Fr::one(), Fr::zero(), Fr::zero()],
+                Fr::zero(),
+                Fr::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fr>::create(
+        gates,
+        params::<Fr, PlonkSpongeConstants5W>("Bls12_381_r", Instance::Legacy),
+        0,
+    )
+    .unwrap();
+    let rng = &mut rand::thread_rng();
+    let srs = kzg::SRS::<Bls12_381>::create(N, rng);
+    let index = Index::<G1Affine, _>::create_with_srs(
+        cs,
+        params::<Fq, PlonkSpongeConstants5W>("Bls12_381_q", Instance::Legacy),
+        endo_coefficient(),
+        SRSValue::Ref(&srs),
+    );
+    let group_map = <G1Affine as CommitmentCurve>::Map::setup();
+
+    let mut witness: [Vec<Fr>; COLUMNS] = array_init(|_| vec![Fr::zero(); N]);
+    for row in 0..N {
+        witness[0][row] = Fr::rand(rng);
+        witness[1][row] = Fr::rand(rng);
+        witness[2][row] = witness[0][row] + &witness[1][row];
+    }
+
+    let mut proof =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    let verify = |proof: &ProverProof<G1Affine, kzg::KzgProof<Bls12_381>>| {
+        ProverProof::verify::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &vec![(&verifier_index, &lgr_comms, proof)],
+        )
+    };
+    assert!(verify(&proof).is_ok());
+
+    proof.proof.quotients.swap(0, 1);
+    assert!(verify(&proof).is_err());
+}
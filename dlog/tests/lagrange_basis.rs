@@ -0,0 +1,100 @@
+/*****************************************************************************************************************
+
+This source file tests the commitments to Lagrange bases cached on SRS and
+the commitments to polynomials given by their evaluations
+
+*****************************************************************************************************************/
+
+use algebra::{AffineCurve, One, ProjectiveCurve, UniformRand, Zero};
+use commitment_dlog::{commitment::PolyComm, srs::SRS};
+use ff_fft::{EvaluationDomain, Evaluations, Radix2EvaluationDomain as D};
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+fn lagrange_commitments(srs: &SRS<Affine>, domain: D<Fp>) -> Vec<PolyComm<Affine>> {
+    (0..domain.size())
+        .map(|i| {
+            let mut v = vec![Fp::zero(); domain.size()];
+            v[i] = Fp::one();
+            let p = Evaluations::<Fp, D<Fp>>::from_vec_and_domain(v, domain).interpolate();
+            srs.commit_non_hiding(&p, None)
+        })
+        .collect()
+}
+
+#[test]
+fn lagrange_basis_test() {
+    let mut srs = SRS::<Affine>::create(1 << 4);
+
+    // domains fitting in a single segment and spanning several ones
+    let small = D::<Fp>::new(1 << 3).unwrap();
+    let large = D::<Fp>::new(1 << 6).unwrap();
+    assert!(srs.lagrange_basis(small).is_none());
+    srs.add_lagrange_basis(small);
+    srs.add_lagrange_basis(large);
+
+    for domain in [small, large].iter() {
+        let basis = srs.lagrange_basis(*domain).unwrap();
+        let expected = lagrange_commitments(&srs, *domain);
+        assert_eq!(basis.len(), domain.size());
+        for (c, e) in basis.iter().zip(expected.iter()) {
+            assert_eq!(c.unshifted, e.unshifted);
+            assert_eq!(c.shifted, None);
+        }
+    }
+    assert_eq!(srs.lagrange_basis(large).unwrap()[0].unshifted.len(), 4);
+}
+
+#[test]
+fn commit_evaluations_test() {
+    let rng = &mut rand::thread_rng();
+    let mut srs = SRS::<Affine>::create(1 << 4);
+    let domain = D::<Fp>::new(1 << 4).unwrap();
+    srs.add_lagrange_basis(domain);
+
+    let evals = Evaluations::<Fp, D<Fp>>::from_vec_and_domain(
+        (0..domain.size()).map(|_| Fp::rand(rng)).collect(),
+        domain,
+    );
+    let comm = srs.commit_evaluations_non_hiding(domain, &evals);
+    let expected = srs.commit_non_hiding(&evals.clone().interpolate(), None);
+    assert_eq!(comm.unshifted, expected.unshifted);
+
+    // the hiding commitment opens with its blinders as the one of the interpolated polynomial
+    let (comm, blinders) = srs.commit_evaluations(domain, &evals, rng);
+    let unblinded = comm.unshifted[0].into_projective() - &srs.h().mul(blinders.unshifted[0]);
+    assert_eq!(unblinded.into_affine(), expected.unshifted[0]);
+}
+
+#[test]
+#[should_panic]
+fn commit_evaluations_without_basis() {
+    let srs = SRS::<Affine>::create(1 << 4);
+    let domain = D::<Fp>::new(1 << 4).unwrap();
+    let evals = Evaluations::<Fp, D<Fp>>::from_vec_and_domain(vec![Fp::one(); 1 << 4], domain);
+    srs.commit_evaluations_non_hiding(domain, &evals);
+}
+
+#[test]
+fn lagrange_basis_serialization() {
+    let mut srs = SRS::<Affine>::create(1 << 4);
+    let domain = D::<Fp>::new(1 << 5).unwrap();
+    srs.add_lagrange_basis(domain);
+
+    let mut bytes = vec![];
+    srs.write_lagrange_bases(&mut bytes).unwrap();
+
+    let mut other = SRS::<Affine>::create(1 << 4);
+    other.read_lagrange_bases(&bytes[..]).unwrap();
+    for (c, e) in other
+        .lagrange_basis(domain)
+        .unwrap()
+        .iter()
+        .zip(srs.lagrange_basis(domain).unwrap().iter())
+    {
+        assert_eq!(c.unshifted, e.unshifted);
+    }
+
+    // the bases are bound to the length of SRS they were computed for
+    let mut shorter = SRS::<Affine>::create(1 << 3);
+    assert!(shorter.read_lagrange_bases(&bytes[..]).is_err());
+}
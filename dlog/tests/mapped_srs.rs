@@ -0,0 +1,96 @@
+/*****************************************************************************************************************
+
+This source file tests the memory-mapped SRS files, reading only the prefixes of the generators
+the circuits need and rejecting the corrupted files
+
+*****************************************************************************************************************/
+
+use commitment_dlog::{
+    mapped_srs::MappedSRS,
+    srs::{SRSSpec, SRSValue, SRS},
+};
+use mina_curves::pasta::vesta::Affine;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+const DEPTH: usize = (1 << 12) + 100;
+
+fn write_file(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}_{}.srs", name, std::process::id()));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn mapped_srs() {
+    let srs = SRS::<Affine>::create(DEPTH);
+    let mut bytes = vec![];
+    MappedSRS::write(&srs, &mut bytes).unwrap();
+    let path = write_file("mapped_srs", &bytes);
+
+    let file = MappedSRS::<Affine>::open(&path).unwrap();
+    assert_eq!(file.max_degree(), DEPTH);
+    assert_eq!(file.h, srs.h());
+    for depth in [0, 1, 1 << 4, 1 << 12, DEPTH].iter() {
+        assert_eq!(file.prefix(*depth).unwrap().g(), &srs.g()[0..*depth]);
+    }
+    assert_eq!(
+        file.prefix(DEPTH + 1).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+
+    // the mapped file is usable wherever SRS is specified
+    let prefix = file.prefix(1 << 5).unwrap();
+    let value = SRSValue::create(SRSSpec::Use(&prefix));
+    assert_eq!(value.get_ref().g(), &srs.g()[0..1 << 5]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn mapped_srs_corruption() {
+    let srs = SRS::<Affine>::create(DEPTH);
+    let mut bytes = vec![];
+    MappedSRS::write(&srs, &mut bytes).unwrap();
+
+    // a corrupted generator of the second block fails only the prefixes spanning it
+    let mut corrupted = bytes.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 1;
+    let path = write_file("mapped_srs_generator", &corrupted);
+    let file = MappedSRS::<Affine>::open(&path).unwrap();
+    assert!(file.prefix(1 << 12).is_ok());
+    assert_eq!(
+        file.prefix((1 << 12) + 1).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    // a corrupted header fails the opening
+    let mut corrupted = bytes.clone();
+    corrupted[30] ^= 1;
+    let path = write_file("mapped_srs_header", &corrupted);
+    assert_eq!(
+        MappedSRS::<Affine>::open(&path).err().unwrap().kind(),
+        ErrorKind::InvalidData
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    // as do the numbers of generators and of generators per block which overflow the offsets
+    // of the file, which are checked before the digest of the header
+    for (position, value) in [(13, u64::MAX), (21, u64::MAX), (21, 0)].iter() {
+        let mut corrupted = bytes.clone();
+        corrupted[*position..*position + 8].copy_from_slice(&value.to_le_bytes());
+        let path = write_file("mapped_srs_overflow", &corrupted);
+        assert_eq!(
+            MappedSRS::<Affine>::open(&path).err().unwrap().kind(),
+            ErrorKind::InvalidData
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // so does a truncated file
+    let path = write_file("mapped_srs_truncated", &bytes[0..bytes.len() - 1]);
+    assert!(MappedSRS::<Affine>::open(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
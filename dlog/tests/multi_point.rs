@@ -0,0 +1,82 @@
+/*****************************************************************************************************************
+
+This source file tests the openings of polynomials at their own sets of evaluation points
+with a single opening proof
+
+*****************************************************************************************************************/
+
+mod common;
+
+use algebra::One;
+use commitment_dlog::{
+    commitment::{CommitmentCurve, MultiPointOpeningProof},
+    srs::SRS,
+};
+use common::{multi_point_batch, open_multi_point, EFqSponge, MultiPointOpening};
+use groupmap::GroupMap;
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+fn open(srs: &SRS<Affine>, group_map: &<Affine as CommitmentCurve>::Map) -> MultiPointOpening {
+    let size = srs.g().len();
+    open_multi_point(
+        srs,
+        group_map,
+        4,
+        &[
+            (size - 1, None, vec![0]),
+            (2 * size - 1, None, vec![0, 1]),
+            (size + size / 2, Some(size + size / 2), vec![2, 1, 3]),
+            (size / 2, None, vec![3]),
+        ],
+    )
+}
+
+fn verify(
+    srs: &SRS<Affine>,
+    group_map: &<Affine as CommitmentCurve>::Map,
+    opening: &MultiPointOpening,
+    proof: &MultiPointOpeningProof<Affine>,
+) -> bool {
+    let rng = &mut rand::thread_rng();
+    let mut batch = multi_point_batch(std::slice::from_ref(opening));
+    batch[0].5 = proof;
+    srs.verify_multi_point::<EFqSponge>(group_map, &mut batch, rng)
+}
+
+#[test]
+fn multi_point_opening() {
+    let srs = SRS::<Affine>::create(1 << 5);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let opening = open(&srs, &group_map);
+
+    // the proof completes the evaluations at the points outside the point sets
+    let lens: Vec<usize> = opening.proof.evals.iter().map(|e| e.len()).collect();
+    assert_eq!(lens, vec![3, 2, 1, 3]);
+    assert!(verify(&srs, &group_map, &opening, &opening.proof));
+}
+
+#[test]
+fn multi_point_opening_with_wrong_evaluation() {
+    let srs = SRS::<Affine>::create(1 << 5);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let mut opening = open(&srs, &group_map);
+
+    opening.comm[2].1[1].1[0] += Fp::one();
+    assert!(!verify(&srs, &group_map, &opening, &opening.proof));
+}
+
+#[test]
+fn multi_point_opening_with_wrong_completion() {
+    let srs = SRS::<Affine>::create(1 << 5);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let opening = open(&srs, &group_map);
+
+    let mut proof = opening.proof.clone();
+    proof.evals[0][1][0] += Fp::one();
+    assert!(!verify(&srs, &group_map, &opening, &proof));
+
+    // the completion has to cover every point outside the point set
+    let mut proof = opening.proof.clone();
+    proof.evals[3].pop();
+    assert!(!verify(&srs, &group_map, &opening, &proof));
+}
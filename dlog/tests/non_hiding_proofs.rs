@@ -0,0 +1,251 @@
+/*****************************************************************************************************************
+
+This source file tests the non-hiding proofs, whose commitments are not blinded, for both the
+3-wire and the 5-wire plonk protocols: they are marked as such and verify as the hiding ones,
+the marker being informational only
+
+*****************************************************************************************************************/
+
+use algebra::{One, UniformRand, Zero};
+use array_init::array_init;
+use commitment_dlog::{
+    commitment::{CommitmentCurve, PolyComm},
+    srs::{endos, SRSSpec, SRS},
+};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    pallas::Affine as Other,
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::{
+    poseidon::{ArithmeticSpongeParams, PlonkSpongeConstants as SC, PlonkSpongeConstants5W},
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+    utils::PolyUtils,
+    FqSponge,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const N: usize = 16;
+
+#[test]
+fn sparse_non_hiding_opening() {
+    let srs = SRS::<Affine>::create(N);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let sponge = DefaultFqSponge::<VestaParameters, SC>::new(oracle::pasta::fq::params());
+    let rng = &mut rand::thread_rng();
+
+    // the L commitment of the last round of the non-hiding opening of 1 + X^2 is the identity,
+    // the upper half of the folded coefficients being zero
+    let p = DensePolynomial::from_coefficients_vec(vec![Fp::one(), Fp::zero(), Fp::one()]);
+    let comm = srs.commit_non_hiding(&p, None);
+    let blinders = PolyComm {
+        unshifted: vec![Fp::zero()],
+        shifted: None,
+    };
+    let x = vec![Fp::rand(rng), Fp::rand(rng)];
+    let (polyscale, evalscale) = (Fp::rand(rng), Fp::rand(rng));
+    let proof = srs.open_maybe_hiding(
+        &group_map,
+        vec![(&p, None, blinders)],
+        &x,
+        polyscale,
+        evalscale,
+        sponge.clone(),
+        false,
+        rng,
+    );
+    assert!(proof.lr.iter().any(|(l, r)| l.is_zero() || r.is_zero()));
+    assert_eq!(proof.validate(), Ok(()));
+
+    let evals = x.iter().map(|x| p.eval(*x, N)).collect::<Vec<_>>();
+    assert!(srs.verify(
+        &group_map,
+        &mut vec![(
+            sponge,
+            x.clone(),
+            polyscale,
+            evalscale,
+            vec![(&comm, evals.iter().collect(), None)],
+            &proof,
+        )],
+        rng
+    ));
+}
+
+#[test]
+fn non_hiding_proofs() {
+    use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
+    use plonk_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // l + r = o on every row
+    let gates = (0..N)
+        .map(|i| {
+            CircuitGate::<Fp>::create_generic(
+                GateWires::wires((i, i), (N + i, N + i), (2 * N + i, 2 * N + i)),
+                Fp::one(),
+                Fp::one(),
+                -Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let mut srs = SRS::create(N);
+    srs.add_lagrange_basis(cs.domain.d1);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let l: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let r: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let o: Vec<Fp> = l.iter().zip(r.iter()).map(|(l, r)| *l + r).collect();
+    let witness = [l, r, o].concat();
+    assert!(index.cs.verify(&witness));
+
+    type EFqSponge = DefaultFqSponge<VestaParameters, SC>;
+    type EFrSponge = DefaultFrSponge<Fp, SC>;
+    let prove = |seed: u64| {
+        ProverProof::create_non_hiding::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &witness,
+            &index,
+            vec![],
+            &mut StdRng::seed_from_u64(seed),
+        )
+        .unwrap()
+    };
+    let (proof1, proof2) = (prove(1), prove(2));
+    assert!(!proof1.hiding);
+    // the commitments are not blinded, only the nonces of delta of the opening proof are drawn
+    assert_eq!(
+        proof1.commitments.z_comm.unshifted,
+        proof2.commitments.z_comm.unshifted
+    );
+    assert_eq!(
+        proof1.commitments.t_comm.unshifted,
+        proof2.commitments.t_comm.unshifted
+    );
+    assert_eq!(proof1.proof.lr, proof2.proof.lr);
+    assert_ne!(proof1.proof.delta, proof2.proof.delta);
+    let proof3 =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+    assert!(proof3.hiding);
+    // the marker is informational only, the transcript not absorbing it
+    let mut proof4 = proof1.clone();
+    proof4.hiding = true;
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    assert!(ProverProof::verify::<EFqSponge, EFrSponge, _>(
+        &group_map,
+        &vec![
+            (&verifier_index, &lgr_comms, &proof1),
+            (&verifier_index, &lgr_comms, &proof2),
+            (&verifier_index, &lgr_comms, &proof3),
+            (&verifier_index, &lgr_comms, &proof4)
+        ]
+    )
+    .is_ok());
+}
+
+#[test]
+fn non_hiding_proofs_5_wires() {
+    use plonk_5_wires_circuits::{
+        constraints::ConstraintSystem, gate::CircuitGate, wires::Wire, wires::COLUMNS,
+    };
+    use plonk_5_wires_protocol_dlog::{index::Index, prover::ProverProof};
+
+    // w0 + w1 = w2 on every row
+    let gates = (0..N)
+        .map(|row| {
+            CircuitGate::<Fp>::create_generic(
+                row,
+                array_init(|col| Wire { row, col }),
+                [Fp::one(), Fp::one(), -Fp::one(), Fp::zero(), Fp::zero()],
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp5::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let mut srs = SRS::create(N);
+    srs.add_lagrange_basis(cs.domain.d1);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq5::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let mut witness: [Vec<Fp>; COLUMNS] = array_init(|_| vec![Fp::zero(); N]);
+    for row in 0..N {
+        witness[0][row] = Fp::rand(rng);
+        witness[1][row] = Fp::rand(rng);
+        witness[2][row] = witness[0][row] + &witness[1][row];
+    }
+
+    type EFqSponge = DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>;
+    type EFrSponge = DefaultFrSponge<Fp, PlonkSpongeConstants5W>;
+    let prove = |seed: u64| {
+        ProverProof::create_non_hiding::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &witness,
+            &index,
+            vec![],
+            &mut StdRng::seed_from_u64(seed),
+        )
+        .unwrap()
+    };
+    let (proof1, proof2) = (prove(1), prove(2));
+    assert!(!proof1.hiding);
+    // the commitments are not blinded, only the nonces of delta of the opening proof are drawn
+    for (w1, w2) in proof1
+        .commitments
+        .w_comm
+        .iter()
+        .zip(proof2.commitments.w_comm.iter())
+    {
+        assert_eq!(w1.unshifted, w2.unshifted);
+    }
+    assert_eq!(
+        proof1.commitments.z_comm.unshifted,
+        proof2.commitments.z_comm.unshifted
+    );
+    assert_eq!(proof1.proof.lr, proof2.proof.lr);
+    assert_ne!(proof1.proof.delta, proof2.proof.delta);
+    let proof3 =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+    assert!(proof3.hiding);
+    // the marker is informational only, the transcript not absorbing it
+    let mut proof4 = proof1.clone();
+    proof4.hiding = true;
+
+    let verifier_index = index.verifier_index();
+    let lgr_comms = vec![];
+    assert!(ProverProof::verify::<EFqSponge, EFrSponge, _>(
+        &group_map,
+        &vec![
+            (&verifier_index, &lgr_comms, &proof1),
+            (&verifier_index, &lgr_comms, &proof2),
+            (&verifier_index, &lgr_comms, &proof3),
+            (&verifier_index, &lgr_comms, &proof4)
+        ]
+    )
+    .is_ok());
+}
@@ -0,0 +1,241 @@
+/*****************************************************************************************************************
+
+This source file tests that the points of opening proofs, SRS files and Plonk proofs are
+validated, the malformed points being rejected with a typed error before the verification
+
+*****************************************************************************************************************/
+
+use algebra::{AffineCurve, FromBytes, One, ToBytes, UniformRand, Zero};
+use commitment_dlog::{
+    commitment::{ChallengePolynomial, CommitmentCurve, OpeningProof, PolyComm},
+    srs::{endos, SRSSpec, SRS},
+    validation::InvalidPoint,
+};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    pallas::Affine as Other,
+    vesta::{Affine, VestaParameters},
+    Fp, Fq,
+};
+use oracle::{
+    poseidon::{ArithmeticSpongeParams, PlonkSpongeConstants as SC},
+    rndoracle::ProofError,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+    FqSponge,
+};
+use std::io::ErrorKind;
+
+// This function returns a point off the curve, the generator with its y coordinate moved
+fn off_curve() -> Affine {
+    let g = Affine::prime_subgroup_generator();
+    let p = Affine::new(g.x, g.y + Fq::one(), false);
+    assert!(!p.is_valid());
+    p
+}
+
+fn opening_proof(srs: &SRS<Affine>) -> OpeningProof<Affine> {
+    let rng = &mut rand::thread_rng();
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let p = DensePolynomial::<Fp>::rand(srs.g().len() - 1, rng);
+    let (_, blinders) = srs.commit(&p, None, rng);
+    srs.open(
+        &group_map,
+        vec![(&p, None, blinders)],
+        &vec![Fp::rand(rng)],
+        Fp::rand(rng),
+        Fp::rand(rng),
+        DefaultFqSponge::<VestaParameters, SC>::new(oracle::pasta::fq::params()),
+        rng,
+    )
+}
+
+#[test]
+fn opening_proof_validation() {
+    let srs = SRS::<Affine>::create(1 << 4);
+    let proof = opening_proof(&srs);
+    assert_eq!(proof.validate(), Ok(()));
+
+    let mut tampered = proof.clone();
+    tampered.delta = off_curve();
+    assert_eq!(
+        tampered.validate(),
+        Err(InvalidPoint::NotOnCurve("opening proof delta"))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.lr[1].0 = off_curve();
+    assert_eq!(
+        tampered.validate(),
+        Err(InvalidPoint::NotOnCurve("opening proof L"))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.delta = Affine::zero();
+    assert_eq!(
+        tampered.validate(),
+        Err(InvalidPoint::Identity("opening proof delta"))
+    );
+}
+
+#[test]
+fn srs_validation() {
+    let srs = SRS::<Affine>::create(1 << 4);
+    assert_eq!(srs.validate(), Ok(()));
+
+    let mut bytes = vec![];
+    srs.write(&mut bytes).unwrap();
+    assert!(SRS::<Affine>::read(&bytes[..]).is_ok());
+
+    let mut g = srs.g().to_vec();
+    g[3] = off_curve();
+    let mut bytes = vec![];
+    SRS::new(g.clone(), srs.h()).write(&mut bytes).unwrap();
+    let err = SRS::<Affine>::read(&bytes[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        err.get_ref().unwrap().downcast_ref::<InvalidPoint>(),
+        Some(&InvalidPoint::NotOnCurve("SRS generator g"))
+    );
+
+    g[3] = Affine::zero();
+    let mut bytes = vec![];
+    SRS::new(g, srs.h()).write(&mut bytes).unwrap();
+    let err = SRS::<Affine>::read(&bytes[..]).unwrap_err();
+    assert_eq!(
+        err.get_ref().unwrap().downcast_ref::<InvalidPoint>(),
+        Some(&InvalidPoint::Identity("SRS generator g"))
+    );
+}
+
+#[test]
+fn plonk_proof_validation() {
+    use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
+    use plonk_protocol_dlog::{
+        index::{Index, VerifierIndex},
+        prover::ProverProof,
+    };
+    const N: usize = 16;
+
+    // l + r = o on every row
+    let gates = (0..N)
+        .map(|i| {
+            CircuitGate::<Fp>::create_generic(
+                GateWires::wires((i, i), (N + i, N + i), (2 * N + i, 2 * N + i)),
+                Fp::one(),
+                Fp::one(),
+                -Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let srs = SRS::create(N);
+    let (endo_q, _endo_r) = endos::<Other>();
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq::params(), endo_q, SRSSpec::Use(&srs));
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let l: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let r: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let o: Vec<Fp> = l.iter().zip(r.iter()).map(|(l, r)| *l + r).collect();
+    let witness = [l, r, o].concat();
+
+    type EFqSponge = DefaultFqSponge<VestaParameters, SC>;
+    type EFrSponge = DefaultFrSponge<Fp, SC>;
+    let proof =
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap();
+
+    let verifier_index = index.verifier_index();
+    assert!(proof.validate(&verifier_index).is_ok());
+    let lgr_comms = vec![];
+    let verify = |verifier_index: &VerifierIndex<Affine>, proof: &ProverProof<Affine>| {
+        ProverProof::verify::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &vec![(verifier_index, &lgr_comms, proof)],
+        )
+    };
+    assert!(verify(&verifier_index, &proof).is_ok());
+
+    let mut tampered = proof.clone();
+    tampered.commitments.z_comm.unshifted[0] = off_curve();
+    assert!(matches!(
+        verify(&verifier_index, &tampered),
+        Err(ProofError::InvalidPoint(InvalidPoint::NotOnCurve(
+            "proof commitment"
+        )))
+    ));
+
+    // the commitments may be the identity, which the sponges absorb, the proof then failing to
+    // verify instead of the verifier panicking
+    let mut tampered = proof.clone();
+    tampered.commitments.z_comm.unshifted[0] = Affine::zero();
+    assert!(verify(&verifier_index, &tampered).is_err());
+
+    let mut tampered = proof.clone();
+    tampered.commitments.t_comm.shifted = Some(Affine::zero());
+    assert!(verify(&verifier_index, &tampered).is_err());
+
+    let mut tampered = proof.clone();
+    tampered.proof.sg = Affine::zero();
+    assert!(matches!(
+        verify(&verifier_index, &tampered),
+        Err(ProofError::InvalidPoint(InvalidPoint::Identity(_)))
+    ));
+
+    // a deserialized proof with more previous challenges than the rounds of an opening proof
+    // against the SRS of the index is rejected before the verifier evaluates their polynomial
+    let mut tampered = proof.clone();
+    let chals = (0..64).map(|_| Fp::rand(rng)).collect();
+    let comm = PolyComm {
+        unshifted: vec![tampered.proof.sg],
+        shifted: None,
+    };
+    tampered.prev_challenges = vec![(ChallengePolynomial::new(chals), comm)];
+    let mut bytes = vec![];
+    tampered.write(&mut bytes).unwrap();
+    let tampered = ProverProof::<Affine>::read(&bytes[..]).unwrap();
+    assert!(matches!(
+        verify(&verifier_index, &tampered),
+        Err(ProofError::PrevChallenges)
+    ));
+
+    // a proof of unexpected shape is rejected before the verifier absorbs or combines it
+    let mut tampered = proof.clone();
+    tampered.commitments.t_comm.shifted = None;
+    assert!(matches!(
+        verify(&verifier_index, &tampered),
+        Err(ProofError::ProofShape("quotient commitment"))
+    ));
+
+    let mut tampered = proof.clone();
+    let max_t_size = (verifier_index.max_quot_size + verifier_index.max_poly_size - 1)
+        / verifier_index.max_poly_size;
+    tampered.commitments.t_comm.unshifted = vec![tampered.proof.sg; max_t_size + 1];
+    assert!(matches!(
+        verify(&verifier_index, &tampered),
+        Err(ProofError::ProofShape("quotient commitment"))
+    ));
+
+    let mut tampered = proof.clone();
+    tampered.evals[1].sigma1.push(Fp::one());
+    assert!(matches!(
+        verify(&verifier_index, &tampered),
+        Err(ProofError::ProofShape("proof evaluations"))
+    ));
+
+    let mut tampered_index = index.verifier_index();
+    tampered_index.ql_comm.unshifted[0] = off_curve();
+    assert_eq!(
+        tampered_index.validate(),
+        Err(InvalidPoint::NotOnCurve("verifier index commitment"))
+    );
+}
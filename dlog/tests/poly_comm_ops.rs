@@ -0,0 +1,102 @@
+/*****************************************************************************************************************
+
+This source file tests the arithmetic on polynomial commitments and the lazily
+evaluated linear combinations of polynomial commitments
+
+*****************************************************************************************************************/
+
+use algebra::{UniformRand, Zero};
+use commitment_dlog::{
+    commitment::{PolyComm, PolyCommCombination},
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+fn assert_comm_eq(a: &PolyComm<Affine>, b: &PolyComm<Affine>) {
+    assert_eq!(a.unshifted, b.unshifted);
+    assert_eq!(a.shifted, b.shifted);
+}
+
+#[test]
+fn poly_comm_arithmetic_test() {
+    let rng = &mut rand::thread_rng();
+    let size = 1 << 4;
+    let srs = SRS::<Affine>::create(size);
+
+    // a spans three segments, b a single one
+    let a = DensePolynomial::<Fp>::rand(3 * size - 1, rng);
+    let b = DensePolynomial::<Fp>::rand(size / 2, rng);
+    let x = Fp::rand(rng);
+    let ca = srs.commit_non_hiding(&a, None);
+    let cb = srs.commit_non_hiding(&b, None);
+
+    assert_comm_eq(&(&ca + &cb), &srs.commit_non_hiding(&(&a + &b), None));
+    assert_comm_eq(&(&cb + &ca), &srs.commit_non_hiding(&(&a + &b), None));
+    assert_comm_eq(&(&ca - &cb), &srs.commit_non_hiding(&(&a - &b), None));
+    assert_comm_eq(&(&cb - &ca), &srs.commit_non_hiding(&(&b - &a), None));
+    assert_comm_eq(
+        &-&ca,
+        &srs.commit_non_hiding(&(&DensePolynomial::zero() - &a), None),
+    );
+    assert_comm_eq(
+        &(&ca * x),
+        &srs.commit_non_hiding(
+            &(&a * &DensePolynomial::from_coefficients_vec(vec![x])),
+            None,
+        ),
+    );
+    assert_comm_eq(&(ca.clone() + cb.clone() - cb.clone()), &ca);
+
+    // the shifted parts are combined, a missing one standing for the commitment to zero
+    let bound = size / 2 + 1;
+    let sa = srs.commit_non_hiding(&b, Some(bound));
+    let sum = &sa + &sa;
+    assert_comm_eq(&sum, &(&sa * Fp::from(2u64)));
+    assert_eq!((&sa + &cb).shifted, sa.shifted);
+    assert_eq!((&cb - &sa).shifted, Some(-sa.shifted.unwrap()));
+}
+
+#[test]
+fn poly_comm_combination_test() {
+    let rng = &mut rand::thread_rng();
+    let size = 1 << 4;
+    let srs = SRS::<Affine>::create(size);
+
+    let polys = vec![
+        (DensePolynomial::<Fp>::rand(size - 1, rng), Some(size)),
+        (DensePolynomial::<Fp>::rand(2 * size - 1, rng), None),
+        (
+            DensePolynomial::<Fp>::rand(size / 2, rng),
+            Some(size / 2 + 1),
+        ),
+        (DensePolynomial::<Fp>::rand(4 * size - 1, rng), None),
+    ];
+    let comms = polys
+        .iter()
+        .map(|(p, bound)| srs.commit_non_hiding(p, *bound))
+        .collect::<Vec<_>>();
+    let scalars = (0..polys.len()).map(|_| Fp::rand(rng)).collect::<Vec<_>>();
+    let scale = Fp::rand(rng);
+
+    let mut combination = PolyCommCombination::new();
+    combination.push(&comms[0], scalars[0]);
+    combination.extend(comms[1..].iter(), scalars[1..].iter().copied());
+    combination.scale(scale);
+    let lazy = combination.eval();
+
+    let eager = comms.iter().zip(scalars.iter()).fold(
+        PolyComm::<Affine> {
+            unshifted: vec![],
+            shifted: None,
+        },
+        |acc, (c, s)| acc + c * (*s * &scale),
+    );
+    assert_eq!(lazy.unshifted.len(), 4);
+    assert_comm_eq(&lazy, &eager);
+
+    // the empty combination is the empty commitment
+    let empty = PolyCommCombination::<Affine>::new().eval();
+    assert!(empty.unshifted.is_empty());
+    assert_eq!(empty.shifted, None);
+}
@@ -0,0 +1,221 @@
+/*****************************************************************************************************************
+
+This source file tests the binary encoding of polynomial commitments,
+opening proofs and Plonk proofs
+
+*****************************************************************************************************************/
+
+use algebra::{AffineCurve, FromBytes, ProjectiveCurve, ToBytes, UniformRand, Zero};
+use array_init::array_init;
+use commitment_dlog::{
+    commitment::{ChallengePolynomial, CommitmentCurve, OpeningProof, PolyComm},
+    srs::SRS,
+};
+use ff_fft::DensePolynomial;
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::poseidon::PlonkSpongeConstants as SC;
+use oracle::sponge::DefaultFqSponge;
+use oracle::FqSponge;
+use rand_core::RngCore;
+
+fn to_bytes<T: ToBytes>(x: &T) -> Vec<u8> {
+    let mut bytes = vec![];
+    x.write(&mut bytes).unwrap();
+    bytes
+}
+
+// checks that decoding and re-encoding reproduces the same bytes
+fn roundtrip<T: ToBytes + FromBytes>(x: &T) -> T {
+    let bytes = to_bytes(x);
+    let y = T::read(&bytes[..]).unwrap();
+    assert_eq!(bytes, to_bytes(&y));
+    y
+}
+
+fn rand_point(rng: &mut dyn RngCore) -> Affine {
+    Affine::prime_subgroup_generator()
+        .mul(Fp::rand(rng))
+        .into_affine()
+}
+
+fn rand_comm(n: usize, shifted: bool, rng: &mut dyn RngCore) -> PolyComm<Affine> {
+    PolyComm {
+        unshifted: (0..n).map(|_| rand_point(rng)).collect(),
+        shifted: if shifted { Some(rand_point(rng)) } else { None },
+    }
+}
+
+fn rand_evals(n: usize, rng: &mut dyn RngCore) -> Vec<Fp> {
+    (0..n).map(|_| Fp::rand(rng)).collect()
+}
+
+fn rand_opening(rng: &mut dyn RngCore) -> OpeningProof<Affine> {
+    OpeningProof {
+        lr: (0..7).map(|_| (rand_point(rng), rand_point(rng))).collect(),
+        delta: rand_point(rng),
+        z1: Fp::rand(rng),
+        z2: Fp::rand(rng),
+        sg: rand_point(rng),
+    }
+}
+
+fn rand_evaluations(rng: &mut dyn RngCore) -> plonk_circuits::scalars::ProofEvaluations<Vec<Fp>> {
+    plonk_circuits::scalars::ProofEvaluations {
+        l: rand_evals(1, rng),
+        r: rand_evals(1, rng),
+        o: rand_evals(1, rng),
+        z: rand_evals(1, rng),
+        t: rand_evals(5, rng),
+        f: rand_evals(1, rng),
+        sigma1: rand_evals(1, rng),
+        sigma2: rand_evals(1, rng),
+    }
+}
+
+fn rand_evaluations_5_wires(
+    rng: &mut dyn RngCore,
+) -> plonk_5_wires_circuits::scalars::ProofEvaluations<Vec<Fp>> {
+    plonk_5_wires_circuits::scalars::ProofEvaluations {
+        w: array_init(|_| rand_evals(1, rng)),
+        z: rand_evals(1, rng),
+        t: rand_evals(7, rng),
+        f: rand_evals(1, rng),
+        s: array_init(|_| rand_evals(1, rng)),
+    }
+}
+
+#[test]
+fn polycomm_serialization() {
+    let rng: &mut dyn RngCore = &mut rand::thread_rng();
+
+    let comm = rand_comm(3, true, rng);
+    let decoded = roundtrip(&comm);
+    assert_eq!(comm.unshifted, decoded.unshifted);
+    assert_eq!(comm.shifted, decoded.shifted);
+
+    let comm = rand_comm(2, false, rng);
+    assert_eq!(roundtrip(&comm).shifted, None);
+
+    // the identity is a valid commitment and has to survive the encoding
+    let comm = PolyComm::<Affine> {
+        unshifted: vec![],
+        shifted: Some(Affine::zero()),
+    };
+    assert_eq!(roundtrip(&comm).shifted, Some(Affine::zero()));
+
+    // points are stored compressed
+    let comm = rand_comm(1, false, rng);
+    assert!(to_bytes(&comm).len() < 8 + 64 + 1);
+
+    // a forged length is not allocated upfront, the reading fails on the missing points
+    let mut bytes = to_bytes(&comm);
+    bytes[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(PolyComm::<Affine>::read(&bytes[..]).is_err());
+}
+
+#[test]
+fn opening_proof_serialization() {
+    let rng = &mut rand::thread_rng();
+
+    let size = 1 << 5;
+    let srs = SRS::<Affine>::create(size);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let sponge = DefaultFqSponge::<VestaParameters, SC>::new(oracle::pasta::fq::params());
+
+    let a = DensePolynomial::<Fp>::rand(size - 1, rng);
+    let (_, blinders) = srs.commit(&a, None, rng);
+    let x = vec![Fp::rand(rng), Fp::rand(rng)];
+
+    let proof = srs.open::<DefaultFqSponge<VestaParameters, SC>>(
+        &group_map,
+        vec![(&a, None, blinders)],
+        &x,
+        Fp::rand(rng),
+        Fp::rand(rng),
+        sponge,
+        rng,
+    );
+
+    let decoded = roundtrip(&proof);
+    assert_eq!(proof.lr, decoded.lr);
+    assert_eq!(proof.delta, decoded.delta);
+    assert_eq!(proof.z1, decoded.z1);
+    assert_eq!(proof.z2, decoded.z2);
+    assert_eq!(proof.sg, decoded.sg);
+}
+
+#[test]
+fn prover_proof_serialization() {
+    use plonk_protocol_dlog::prover::{ProverCommitments, ProverProof};
+
+    let rng: &mut dyn RngCore = &mut rand::thread_rng();
+
+    let proof = ProverProof::<Affine> {
+        commitments: ProverCommitments {
+            l_comm: rand_comm(1, false, rng),
+            r_comm: rand_comm(1, false, rng),
+            o_comm: rand_comm(1, false, rng),
+            z_comm: rand_comm(1, false, rng),
+            t_comm: rand_comm(5, true, rng),
+        },
+        proof: rand_opening(rng),
+        evals: [rand_evaluations(rng), rand_evaluations(rng)],
+        public: rand_evals(6, rng),
+        prev_challenges: vec![(
+            ChallengePolynomial::new(rand_evals(7, rng)),
+            rand_comm(1, false, rng),
+        )],
+        hiding: true,
+    };
+
+    let decoded = roundtrip(&proof);
+    assert_eq!(proof.public, decoded.public);
+    assert_eq!(proof.evals[1].t, decoded.evals[1].t);
+    assert_eq!(
+        proof.prev_challenges[0].0.chals,
+        decoded.prev_challenges[0].0.chals
+    );
+    assert_eq!(decoded.hiding, true);
+
+    // a corrupted header is rejected
+    let mut bytes = to_bytes(&proof);
+    bytes[0] ^= 1;
+    assert!(ProverProof::<Affine>::read(&bytes[..]).is_err());
+
+    // a truncated proof is rejected
+    let bytes = to_bytes(&proof);
+    assert!(ProverProof::<Affine>::read(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn prover_proof_5_wires_serialization() {
+    use plonk_5_wires_protocol_dlog::prover::{ProverCommitments, ProverProof};
+
+    let rng: &mut dyn RngCore = &mut rand::thread_rng();
+
+    let proof = ProverProof::<Affine> {
+        commitments: ProverCommitments {
+            w_comm: array_init(|_| rand_comm(1, false, rng)),
+            z_comm: rand_comm(1, false, rng),
+            t_comm: rand_comm(7, true, rng),
+        },
+        proof: rand_opening(rng),
+        evals: [rand_evaluations_5_wires(rng), rand_evaluations_5_wires(rng)],
+        public: vec![],
+        prev_challenges: vec![],
+        hiding: false,
+    };
+
+    let decoded = roundtrip(&proof);
+    assert_eq!(proof.evals[0].w[4], decoded.evals[0].w[4]);
+    assert_eq!(proof.evals[1].s[3], decoded.evals[1].s[3]);
+    assert_eq!(decoded.hiding, false);
+
+    // proofs of the 3 and 5 wire systems are not interchangeable
+    let bytes = to_bytes(&proof);
+    assert!(plonk_protocol_dlog::prover::ProverProof::<Affine>::read(&bytes[..]).is_err());
+}
@@ -0,0 +1,74 @@
+/*****************************************************************************************************************
+
+This source file tests the parallel and incremental generation of SRS and the verification
+of the derivation of its generators
+
+*****************************************************************************************************************/
+
+use commitment_dlog::srs::SRS;
+use ff_fft::{EvaluationDomain, Radix2EvaluationDomain as D};
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+#[test]
+fn srs_extension() {
+    let mut srs = SRS::<Affine>::create(1 << 4);
+    srs.add_lagrange_basis(D::<Fp>::new(1 << 3).unwrap());
+    srs.add_lagrange_basis(D::<Fp>::new(1 << 6).unwrap());
+    srs.add_fixed_base_table(4);
+
+    srs.extend(1 << 5);
+    let mut fresh = SRS::<Affine>::create(1 << 5);
+    assert_eq!(srs.g(), fresh.g());
+    assert_eq!(srs.h(), fresh.h());
+
+    // the Lagrange bases spanning several segments of the former SRS are dropped
+    assert!(srs.lagrange_basis(D::<Fp>::new(1 << 6).unwrap()).is_none());
+    fresh.add_lagrange_basis(D::<Fp>::new(1 << 3).unwrap());
+    let (basis, fresh_basis) = (
+        srs.lagrange_basis(D::<Fp>::new(1 << 3).unwrap()).unwrap(),
+        fresh.lagrange_basis(D::<Fp>::new(1 << 3).unwrap()).unwrap(),
+    );
+    assert!(basis
+        .iter()
+        .zip(fresh_basis.iter())
+        .all(|(b, f)| b.unshifted == f.unshifted));
+
+    fresh.add_fixed_base_table(4);
+    assert_eq!(
+        srs.fixed_base.unwrap().multiples,
+        fresh.fixed_base.unwrap().multiples
+    );
+}
+
+#[test]
+fn srs_generators_verification() {
+    let rng = &mut rand::thread_rng();
+    let srs = SRS::<Affine>::create(1 << 6);
+    assert!(srs.verify_generators(16, rng));
+
+    let mut g = srs.g().to_vec();
+    g[5] = g[6];
+    let srs = SRS::new(g, srs.h());
+    assert!(!srs.verify_generators(srs.g().len() * 32, rng));
+}
+
+#[test]
+fn srs_prefix() {
+    let srs = SRS::<Affine>::create(200);
+    for n in [0, 1, 64, 100, 128, 200].iter() {
+        assert!(SRS::<Affine>::create(*n).is_prefix_of(&srs));
+    }
+    assert!(!srs.is_prefix_of(&SRS::<Affine>::create(100)));
+
+    // SRS differing in a generator covered by a digest or past the last digest is not a prefix
+    for (i, j) in [(10, 11), (99, 98)].iter() {
+        let mut g = srs.g()[0..100].to_vec();
+        g[*i] = g[*j];
+        assert!(!SRS::new(g, srs.h()).is_prefix_of(&srs));
+    }
+
+    // the extended SRS has the digests of the created one
+    let mut extended = SRS::<Affine>::create(100);
+    extended.extend(200);
+    assert!(srs.is_prefix_of(&extended) && extended.is_prefix_of(&srs));
+}
@@ -0,0 +1,73 @@
+/*****************************************************************************************************************
+
+This source file tests that the commitments of polynomials streamed from iterators and readers
+match the commitments of the polynomials held in memory
+
+*****************************************************************************************************************/
+
+use algebra::{UniformRand, Zero};
+use commitment_dlog::{serialization::write_fields, srs::SRS};
+use ff_fft::DensePolynomial;
+use mina_curves::pasta::{vesta::Affine, Fp};
+
+#[test]
+fn streaming_commitment() {
+    let rng = &mut rand::thread_rng();
+    let size = 1 << 4;
+    let srs = SRS::<Affine>::create(size);
+
+    let mut padded = DensePolynomial::<Fp>::rand(size / 2, rng);
+    padded.coeffs.resize(3 * size, Fp::zero());
+    let polys = vec![
+        (DensePolynomial::<Fp>::zero(), None),
+        (DensePolynomial::<Fp>::zero(), Some(size / 2)),
+        (DensePolynomial::<Fp>::rand(size / 2, rng), None),
+        (DensePolynomial::<Fp>::rand(4 * size - 1, rng), None),
+        (DensePolynomial::<Fp>::rand(37 * size + 3, rng), None),
+        (
+            DensePolynomial::<Fp>::rand(2 * size + 5, rng),
+            Some(2 * size + 5),
+        ),
+        (DensePolynomial::<Fp>::rand(size - 1, rng), Some(3 * size)),
+        (
+            DensePolynomial::<Fp>::rand(2 * size - 1, rng),
+            Some(2 * size),
+        ),
+        (padded, None),
+    ];
+
+    for (p, bound) in polys.iter() {
+        let c = srs.commit_non_hiding(p, *bound);
+
+        let c_iter = srs.commit_non_hiding_iter(p.coeffs.iter().copied(), *bound);
+        assert_eq!(c.unshifted, c_iter.unshifted);
+        assert_eq!(c.shifted, c_iter.shifted);
+
+        let mut bytes = vec![];
+        write_fields(&p.coeffs, &mut bytes).unwrap();
+        let c_reader = srs.commit_non_hiding_reader(&bytes[..], *bound).unwrap();
+        assert_eq!(c.unshifted, c_reader.unshifted);
+        assert_eq!(c.shifted, c_reader.shifted);
+
+        // the hiding commitment has a blinder per segment
+        let (c_hiding, w) = srs.commit_iter(p.coeffs.iter().copied(), *bound, rng);
+        assert_eq!(
+            c_hiding.unshifted.len(),
+            std::cmp::max(c.unshifted.len(), 1)
+        );
+        assert_eq!(w.unshifted.len(), c_hiding.unshifted.len());
+        assert_eq!(w.shifted.is_some(), c.shifted.is_some());
+    }
+}
+
+#[test]
+fn streaming_commitment_of_truncated_reader() {
+    let rng = &mut rand::thread_rng();
+    let srs = SRS::<Affine>::create(1 << 4);
+    let p = DensePolynomial::<Fp>::rand(40, rng);
+
+    let mut bytes = vec![];
+    write_fields(&p.coeffs, &mut bytes).unwrap();
+    bytes.truncate(bytes.len() - 1);
+    assert!(srs.commit_non_hiding_reader(&bytes[..], None).is_err());
+}
@@ -0,0 +1,166 @@
+/*****************************************************************************************************************
+
+This source file tests the labelled transcripts of the Plonk proofs: the traces of the prover and
+of the verifier are the same, and a tampered proof makes the verifier diverge at the labelled step
+of the tampered value
+
+*****************************************************************************************************************/
+
+use algebra::{AffineCurve, One, ProjectiveCurve, UniformRand, Zero};
+use commitment_dlog::{
+    commitment::CommitmentCurve,
+    srs::{endos, SRSSpec, SRS},
+};
+use groupmap::GroupMap;
+use mina_curves::pasta::{
+    pallas::Affine as Other,
+    vesta::{Affine, VestaParameters},
+    Fp,
+};
+use oracle::{
+    poseidon::{ArithmeticSpongeParams, PlonkSpongeConstants as SC},
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+    transcript::{record, Event, EventKind, Trace, Transcript},
+};
+use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
+use plonk_protocol_dlog::{
+    index::{Index, VerifierIndex},
+    prover::ProverProof,
+};
+
+type EFqSponge = Transcript<DefaultFqSponge<VestaParameters, SC>>;
+type EFrSponge = Transcript<DefaultFrSponge<Fp, SC>>;
+
+const N: usize = 16;
+
+fn index(srs: &SRS<Affine>) -> Index<Affine> {
+    // l + r = o on every row
+    let gates = (0..N)
+        .map(|i| {
+            CircuitGate::<Fp>::create_generic(
+                GateWires::wires((i, i), (N + i, N + i), (2 * N + i, 2 * N + i)),
+                Fp::one(),
+                Fp::one(),
+                -Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+            )
+        })
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
+        0,
+    )
+    .unwrap();
+    let (endo_q, _endo_r) = endos::<Other>();
+    Index::<Affine>::create(cs, oracle::pasta::fq::params(), endo_q, SRSSpec::Use(srs))
+}
+
+fn verify(verifier_index: &VerifierIndex<Affine>, proof: &ProverProof<Affine>) -> (bool, Trace) {
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let lgr_comms = vec![];
+    record(|| {
+        ProverProof::verify::<EFqSponge, EFrSponge, _>(
+            &group_map,
+            &vec![(verifier_index, &lgr_comms, proof)],
+        )
+        .is_ok()
+    })
+}
+
+#[test]
+fn prover_and_verifier_traces() {
+    let srs = SRS::create(N);
+    let index = index(&srs);
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+
+    let rng = &mut rand::thread_rng();
+    let l: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let r: Vec<Fp> = (0..N).map(|_| Fp::rand(rng)).collect();
+    let o: Vec<Fp> = l.iter().zip(r.iter()).map(|(l, r)| *l + r).collect();
+    let witness = [l, r, o].concat();
+
+    let (proof, prover) = record(|| {
+        ProverProof::create::<EFqSponge, EFrSponge, _>(&group_map, &witness, &index, vec![])
+            .unwrap()
+    });
+    let verifier_index = index.verifier_index();
+    let (verified, verifier) = verify(&verifier_index, &proof);
+    assert!(verified);
+    assert_eq!(
+        prover.first_divergence(&verifier),
+        None,
+        "\n{}",
+        prover.side_by_side(&verifier)
+    );
+
+    // every step of the argument is labelled, in order
+    let mut labels: Vec<&str> = prover.0.iter().map(|e| e.label).collect();
+    labels.dedup();
+    assert_eq!(
+        labels,
+        vec![
+            "witness",
+            "permutation",
+            "quotient",
+            "evaluations",
+            "opening"
+        ]
+    );
+
+    // nothing is recorded out of recording
+    let (_, empty) = record(|| ());
+    assert_eq!(empty, Trace::default());
+
+    // the verifier of a proof with tampered evaluations diverges when absorbing them
+    let mut tampered = proof.clone();
+    tampered.evals[0].l[0] += &Fp::one();
+    let (verified, verifier) = verify(&verifier_index, &tampered);
+    assert!(!verified);
+    let i = prover.first_divergence(&verifier).unwrap();
+    assert_eq!(prover.0[i].label, "evaluations");
+    assert_eq!(prover.0[i].kind, EventKind::Absorb);
+    assert!(prover
+        .side_by_side(&verifier)
+        .contains(&format!(">> {:4} |", i)));
+
+    // and the verifier of a proof with a tampered commitment when absorbing it
+    let mut tampered = proof.clone();
+    let z = (tampered.commitments.z_comm.unshifted[0].into_projective()
+        + &Affine::prime_subgroup_generator().into_projective())
+        .into_affine();
+    tampered.commitments.z_comm.unshifted[0] = z;
+    let (verified, verifier) = verify(&verifier_index, &tampered);
+    assert!(!verified);
+    let i = prover.first_divergence(&verifier).unwrap();
+    assert_eq!(prover.0[i].label, "permutation");
+    assert_eq!(verifier.0[i].values, vec![format!("{:?}", z)]);
+}
+
+#[test]
+fn trace_divergence() {
+    let event = |label, kind| Event {
+        label,
+        kind,
+        values: vec!["0".to_string()],
+    };
+    let a = Trace(vec![
+        event("a", EventKind::Absorb),
+        event("a", EventKind::Squeeze),
+    ]);
+    assert_eq!(a.first_divergence(&a), None);
+    assert!(!a.side_by_side(&a).contains(">>"));
+
+    // a trace stopping early diverges at its end
+    let b = Trace(vec![event("a", EventKind::Absorb)]);
+    assert_eq!(a.first_divergence(&b), Some(1));
+    assert_eq!(b.first_divergence(&a), Some(1));
+    assert!(a.side_by_side(&b).contains("<end>"));
+
+    let c = Trace(vec![
+        event("a", EventKind::Absorb),
+        event("b", EventKind::Squeeze),
+    ]);
+    assert_eq!(a.first_divergence(&c), Some(1));
+}
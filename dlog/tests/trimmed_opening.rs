@@ -0,0 +1,57 @@
+/*****************************************************************************************************************
+
+This source file tests that opening proofs are trimmed to the prefix of SRS covering
+the committed polynomials
+
+*****************************************************************************************************************/
+
+mod common;
+
+use commitment_dlog::{commitment::CommitmentCurve, srs::SRS};
+use common::{batch, open, EFqSponge, Opening};
+use groupmap::GroupMap;
+use mina_curves::pasta::vesta::Affine;
+
+fn verify(
+    srs: &SRS<Affine>,
+    group_map: &<Affine as CommitmentCurve>::Map,
+    opening: &Opening,
+) -> bool {
+    // the sponges are consumed by the verification, so every check gets a fresh batch
+    let openings = std::slice::from_ref(opening);
+    let rng = &mut rand::thread_rng();
+    let verified = srs.verify::<EFqSponge>(group_map, &mut batch(openings), rng);
+    let deferred = match srs.succinct_verify::<EFqSponge>(group_map, &mut batch(openings), rng) {
+        Some(accumulators) => srs.check_accumulators(&accumulators, rng),
+        None => false,
+    };
+    assert_eq!(verified, deferred);
+    verified
+}
+
+#[test]
+fn trimmed_opening_test() {
+    let group_map = <Affine as CommitmentCurve>::Map::setup();
+    let size = 1 << 7;
+    let srs = SRS::<Affine>::create(size + 3);
+    let prefix = SRS::<Affine>::create(16);
+
+    // small polynomials are opened against the first 16 generators only
+    let opening = open(&srs, &group_map, &[(4, None), (15, None), (8, None)]);
+    assert_eq!(opening.proof.lr.len(), 4);
+    assert!(verify(&srs, &group_map, &opening));
+    // SRS is deterministic, so the opening also verifies against the shorter SRS
+    assert!(verify(&prefix, &group_map, &opening));
+
+    // polynomials spanning several segments are opened against the whole SRS
+    let opening = open(&srs, &group_map, &[(4, None), (3 * size - 1, None)]);
+    assert_eq!(opening.proof.lr.len(), 8);
+    assert!(verify(&srs, &group_map, &opening));
+    // and are rejected, without panicking, by an SRS too short for them
+    assert!(!verify(&prefix, &group_map, &opening));
+
+    // degree bounded segments are shifted to the end of SRS
+    let opening = open(&srs, &group_map, &[(4, Some(7)), (8, None)]);
+    assert_eq!(opening.proof.lr.len(), 8);
+    assert!(verify(&srs, &group_map, &opening));
+}
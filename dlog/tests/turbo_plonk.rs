@@ -24,10 +24,9 @@ This source file tests constraints for the following computatios:
 use algebra::{Field, One, UniformRand, Zero};
 use colored::Colorize;
 use commitment_dlog::{
-    commitment::{b_poly_coefficients, ceil_log2, CommitmentCurve},
+    commitment::{ceil_log2, ChallengePolynomial, CommitmentCurve},
     srs::{SRSSpec, SRS},
 };
-use ff_fft::{DensePolynomial, Evaluations, Radix2EvaluationDomain as D};
 use groupmap::GroupMap;
 use mina_curves::pasta::{
     pallas::Affine as Other,
@@ -37,6 +36,7 @@ use mina_curves::pasta::{
 use oracle::poseidon::*;
 use oracle::{
     poseidon::{ArithmeticSponge, ArithmeticSpongeParams, PlonkSpongeConstants as SC, Sponge},
+    rndoracle::FailedCheck,
     sponge::{DefaultFqSponge, DefaultFrSponge},
 };
 use plonk_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::GateWires};
@@ -504,19 +504,17 @@ fn turbo_plonk() {
     }
 
     let (endo_q, _endo_r) = commitment_dlog::srs::endos::<Other>();
-    let srs = SRS::create(MAX_SIZE);
-
-    let index = Index::<Affine>::create(
-        ConstraintSystem::<Fp>::create(
-            gates,
-            oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
-            PUBLIC,
-        )
-        .unwrap(),
-        oracle::pasta::fq::params(),
-        endo_q,
-        SRSSpec::Use(&srs),
-    );
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp::params() as ArithmeticSpongeParams<Fp>,
+        PUBLIC,
+    )
+    .unwrap();
+    let mut srs = SRS::create(MAX_SIZE);
+    srs.add_lagrange_basis(cs.domain.d1);
+
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq::params(), endo_q, SRSSpec::Use(&srs));
 
     positive(&index);
     negative(&index);
@@ -535,16 +533,12 @@ where
     let points = sample_points();
     let group_map = <Affine as CommitmentCurve>::Map::setup();
 
-    let lgr_comms: Vec<_> = (0..PUBLIC)
-        .map(|i| {
-            let mut v = vec![Fp::zero(); i + 1];
-            v[i] = Fp::one();
-
-            let p =
-                Evaluations::<Fp, D<Fp>>::from_vec_and_domain(v, index.cs.domain.d1).interpolate();
-            index.srs.get_ref().commit_non_hiding(&p, None)
-        })
-        .collect();
+    let lgr_comms = index
+        .srs
+        .get_ref()
+        .lagrange_basis(index.cs.domain.d1)
+        .unwrap()[0..PUBLIC]
+        .to_vec();
 
     println!("{}", "Prover 100 zk-proofs computation".green());
     let mut start = Instant::now();
@@ -757,18 +751,15 @@ where
         assert_eq!(index.cs.verify(&witness), true);
 
         let prev = {
-            let k = ceil_log2(index.srs.get_ref().g.len());
-            let chals: Vec<_> = (0..k).map(|_| Fp::rand(rng)).collect();
-            let comm = {
-                let b = DensePolynomial::from_coefficients_vec(b_poly_coefficients(&chals));
-                index.srs.get_ref().commit_non_hiding(&b, None)
-            };
-            (chals, comm)
+            let k = ceil_log2(index.srs.get_ref().g().len());
+            let mut b = ChallengePolynomial::new((0..k).map(|_| Fp::rand(rng)).collect());
+            let comm = b.commit(index.srs.get_ref());
+            (b, comm)
         };
 
         // add the proof to the batch
         batch.push(
-            ProverProof::create::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>>(
+            ProverProof::create::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>, _>(
                 &group_map,
                 &witness,
                 &index,
@@ -783,7 +774,7 @@ where
     println!("{}{:?}", "Execution time: ".yellow(), start.elapsed());
 
     // verify one proof serially
-    match ProverProof::verify::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>>(
+    match ProverProof::verify::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>, _>(
         &group_map,
         &vec![(&verifier_index, &lgr_comms, &batch[0])],
     ) {
@@ -798,7 +789,7 @@ where
         .iter()
         .map(|p| (&verifier_index, &lgr_comms, p))
         .collect();
-    match ProverProof::verify::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>>(
+    match ProverProof::verify::<DefaultFqSponge<VestaParameters, SC>, DefaultFrSponge<Fp, SC>, _>(
         &group_map, &batch,
     ) {
         Err(error) => panic!("Failure verifying the prover's proofs in batch: {}", error),
@@ -806,6 +797,38 @@ where
             println!("{}{:?}", "Execution time: ".yellow(), start.elapsed());
         }
     }
+
+    // the failing proofs of a batch are pointed out with the check they fail
+    let mut bad_evals = batch[1].2.clone();
+    bad_evals.evals[0].t[0] += &Fp::one();
+    let mut bad_opening = batch[2].2.clone();
+    bad_opening.proof.z1 += &Fp::one();
+    let mut bad_point = batch[3].2.clone();
+    bad_point.proof.sg = Affine::zero();
+    let mut tampered = batch.clone();
+    tampered[1].2 = &bad_evals;
+    tampered[2].2 = &bad_opening;
+    tampered[3].2 = &bad_point;
+    assert_eq!(
+        ProverProof::verify_failures::<
+            DefaultFqSponge<VestaParameters, SC>,
+            DefaultFrSponge<Fp, SC>,
+        >(&group_map, &batch)
+        .unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        ProverProof::verify_failures::<
+            DefaultFqSponge<VestaParameters, SC>,
+            DefaultFrSponge<Fp, SC>,
+        >(&group_map, &tampered)
+        .unwrap(),
+        vec![
+            (1, FailedCheck::Linearization),
+            (2, FailedCheck::Ipa),
+            (3, FailedCheck::Validation)
+        ]
+    );
 }
 
 fn negative(index: &Index<Affine>)
@@ -31,13 +31,14 @@ This source file tests constraints for the following computations:
 
 **********************************************************************************************************/
 
-use algebra::{BigInteger, Field, One, PrimeField, SquareRootField, UniformRand, Zero};
+use algebra::{
+    AffineCurve, BigInteger, Field, One, PrimeField, SquareRootField, UniformRand, Zero,
+};
 use colored::Colorize;
 use commitment_dlog::{
-    commitment::{b_poly_coefficients, ceil_log2, CommitmentCurve},
+    commitment::{ceil_log2, ChallengePolynomial, CommitmentCurve},
     srs::{endos, SRSSpec, SRS},
 };
-use ff_fft::{DensePolynomial, Evaluations, Radix2EvaluationDomain as D};
 use groupmap::GroupMap;
 use mina_curves::pasta::{
     pallas::Affine as Other,
@@ -48,6 +49,7 @@ use oracle::{
     poseidon::{
         ArithmeticSponge, ArithmeticSpongeParams, PlonkSpongeConstants5W, Sponge, SpongeConstants,
     },
+    rndoracle::{FailedCheck, ProofError},
     sponge::{DefaultFqSponge, DefaultFrSponge},
 };
 use plonk_5_wires_circuits::{constraints::ConstraintSystem, gate::CircuitGate, wires::Wire};
@@ -816,20 +818,18 @@ fn turbo_plonk() {
         ],
     ));
 
-    let srs = SRS::create(MAX_SIZE);
+    let cs = ConstraintSystem::<Fp>::create(
+        gates,
+        oracle::pasta::fp5::params() as ArithmeticSpongeParams<Fp>,
+        PUBLIC,
+    )
+    .unwrap();
+    let mut srs = SRS::create(MAX_SIZE);
+    srs.add_lagrange_basis(cs.domain.d1);
 
     let (endo_q, _endo_r) = endos::<Other>();
-    let index = Index::<Affine>::create(
-        ConstraintSystem::<Fp>::create(
-            gates,
-            oracle::pasta::fp5::params() as ArithmeticSpongeParams<Fp>,
-            PUBLIC,
-        )
-        .unwrap(),
-        oracle::pasta::fq5::params(),
-        endo_q,
-        SRSSpec::Use(&srs),
-    );
+    let index =
+        Index::<Affine>::create(cs, oracle::pasta::fq5::params(), endo_q, SRSSpec::Use(&srs));
 
     positive(&index);
     negative(&index);
@@ -839,16 +839,12 @@ fn positive(index: &Index<Affine>) {
     let rng = &mut OsRng;
     let mut batch = Vec::new();
     let group_map = <Affine as CommitmentCurve>::Map::setup();
-    let lgr_comms: Vec<_> = (0..PUBLIC)
-        .map(|i| {
-            let mut v = vec![Fp::zero(); i + 1];
-            v[i] = Fp::one();
-
-            let p =
-                Evaluations::<Fp, D<Fp>>::from_vec_and_domain(v, index.cs.domain.d1).interpolate();
-            index.srs.get_ref().commit_non_hiding(&p, None)
-        })
-        .collect();
+    let lgr_comms = index
+        .srs
+        .get_ref()
+        .lagrange_basis(index.cs.domain.d1)
+        .unwrap()[0..PUBLIC]
+        .to_vec();
     let mut w = || -> Fp { Fp::rand(rng) };
 
     println!("{}", "Prover 10 zk-proofs computation".green());
@@ -1175,13 +1171,10 @@ fn positive(index: &Index<Affine>) {
         assert_eq!(index.cs.verify(&witness), true);
 
         let prev = {
-            let k = ceil_log2(index.srs.get_ref().g.len());
-            let chals: Vec<_> = (0..k).map(|_| w()).collect();
-            let comm = {
-                let b = DensePolynomial::from_coefficients_vec(b_poly_coefficients(&chals));
-                index.srs.get_ref().commit_non_hiding(&b, None)
-            };
-            (chals, comm)
+            let k = ceil_log2(index.srs.get_ref().g().len());
+            let mut b = ChallengePolynomial::new((0..k).map(|_| w()).collect());
+            let comm = b.commit(index.srs.get_ref());
+            (b, comm)
         };
 
         // add the proof to the batch
@@ -1189,6 +1182,7 @@ fn positive(index: &Index<Affine>) {
             ProverProof::create::<
                 DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
                 DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+                _,
             >(&group_map, &witness, &index, vec![prev])
             .unwrap(),
         );
@@ -1202,6 +1196,7 @@ fn positive(index: &Index<Affine>) {
     match ProverProof::verify::<
         DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
         DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+        _,
     >(&group_map, &vec![(&verifier_index, &lgr_comms, &batch[0])])
     {
         Err(error) => panic!("Failure verifying the prover's proof: {}", error),
@@ -1218,6 +1213,7 @@ fn positive(index: &Index<Affine>) {
     match ProverProof::verify::<
         DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
         DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+        _,
     >(&group_map, &batch)
     {
         Err(error) => panic!("Failure verifying the prover's proofs in batch: {}", error),
@@ -1225,6 +1221,67 @@ fn positive(index: &Index<Affine>) {
             println!("{}{:?}", "Execution time: ".yellow(), start.elapsed());
         }
     }
+
+    // the failing proofs of a batch are pointed out with the check they fail
+    let mut bad_evals = batch[1].2.clone();
+    bad_evals.evals[0].t[0] += &Fp::one();
+    let mut bad_opening = batch[2].2.clone();
+    bad_opening.proof.z1 += &Fp::one();
+    let mut bad_point = batch[3].2.clone();
+    bad_point.proof.sg = Affine::zero();
+    let mut tampered = batch.clone();
+    tampered[1].2 = &bad_evals;
+    tampered[2].2 = &bad_opening;
+    tampered[3].2 = &bad_point;
+    assert_eq!(
+        ProverProof::verify_failures::<
+            DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
+            DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+        >(&group_map, &batch)
+        .unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        ProverProof::verify_failures::<
+            DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
+            DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+        >(&group_map, &tampered)
+        .unwrap(),
+        vec![
+            (1, FailedCheck::Linearization),
+            (2, FailedCheck::Ipa),
+            (3, FailedCheck::Validation)
+        ]
+    );
+
+    // malformed proofs are rejected with a typed error instead of the verifier panicking
+    let verify = |proof: &ProverProof<Affine>| {
+        ProverProof::verify::<
+            DefaultFqSponge<VestaParameters, PlonkSpongeConstants5W>,
+            DefaultFrSponge<Fp, PlonkSpongeConstants5W>,
+            _,
+        >(&group_map, &vec![(&verifier_index, &lgr_comms, proof)])
+    };
+    let mut unshifted = batch[0].2.clone();
+    unshifted.commitments.t_comm.shifted = None;
+    let mut long = batch[0].2.clone();
+    let max_t_size = (verifier_index.max_quot_size + verifier_index.max_poly_size - 1)
+        / verifier_index.max_poly_size;
+    long.commitments.t_comm.unshifted = vec![Affine::prime_subgroup_generator(); max_t_size + 1];
+    let mut ragged = batch[0].2.clone();
+    ragged.evals[1].w[2].push(Fp::one());
+    for (proof, location) in [
+        (unshifted, "quotient commitment"),
+        (long, "quotient commitment"),
+        (ragged, "proof evaluations"),
+    ]
+    .iter()
+    {
+        assert!(matches!(
+            verify(proof),
+            Err(ProofError::ProofShape(l)) if l == *location
+        ));
+    }
 }
 
 fn negative(index: &Index<Affine>) {
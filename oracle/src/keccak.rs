@@ -0,0 +1,199 @@
+/*****************************************************************************************************************
+
+This source file implements the Keccak-256 sponges of the random oracle arguments
+
+The sponges hash the canonical encodings of the absorbed values instead of absorbing them into the
+state of a Poseidon permutation, so that the proofs can be verified where Keccak is cheap and
+field arithmetic is not:
+
+1. a field element is encoded as its canonical integer in big-endian bytes, of the byte length of
+    the modulus
+2. an affine point is encoded as its x and y coordinates, the point at infinity as the
+    coordinates (0, 0), as the Poseidon sponges absorb it
+
+The state is the digest of the last squeeze followed by the values absorbed since. A squeeze
+outputs Keccak-256 of the state and a 0 byte, the next state being Keccak-256 of the state and
+a 1 byte. The challenges are the low 128 bits of the outputs, the field elements their low bits
+below the capacity of the field.
+
+*****************************************************************************************************************/
+
+use crate::poseidon::ArithmeticSpongeParams;
+use crate::sponge::{ScalarChallenge, CHALLENGE_LENGTH_IN_LIMBS};
+use crate::FqSponge;
+use algebra::{
+    curves::{short_weierstrass_jacobian::GroupAffine, SWModelParameters},
+    Field, FpParameters, PrimeField, Zero,
+};
+use sha3::{Digest, Keccak256};
+use std::marker::PhantomData;
+
+const OUTPUT: u8 = 0;
+const NEXT_STATE: u8 = 1;
+
+// This function encodes the field element as the big-endian bytes of its canonical integer
+pub fn field_bytes<F: PrimeField>(x: &F) -> Vec<u8> {
+    let len = (F::size_in_bits() + 7) / 8;
+    let repr = x.into_repr();
+    let bytes: Vec<u8> = repr
+        .as_ref()
+        .iter()
+        .rev()
+        .flat_map(|limb| limb.to_be_bytes().to_vec())
+        .collect();
+    bytes[bytes.len() - len..].to_vec()
+}
+
+// This function encodes the affine point as its coordinates, the point at infinity as (0, 0)
+// which is not on the curves with a nonzero b
+pub fn point_bytes<P: SWModelParameters>(g: &GroupAffine<P>) -> Vec<u8>
+where
+    P::BaseField: PrimeField,
+{
+    if g.infinity {
+        let zero = field_bytes(&P::BaseField::zero());
+        [&zero[..], &zero[..]].concat()
+    } else {
+        [field_bytes(&g.x), field_bytes(&g.y)].concat()
+    }
+}
+
+// The Keccak-256 sponge over byte strings
+#[derive(Clone, Debug, Default)]
+pub struct KeccakSponge {
+    pub state: Vec<u8>,
+    pub last_squeezed: Vec<u64>,
+}
+
+impl KeccakSponge {
+    pub fn absorb(&mut self, bytes: &[u8]) {
+        self.last_squeezed = vec![];
+        self.state.extend_from_slice(bytes);
+    }
+
+    // This function squeezes 256 bits as four little-endian limbs
+    pub fn squeeze(&mut self) -> Vec<u64> {
+        let hash = |domain: u8| Keccak256::digest(&[&self.state[..], &[domain]].concat());
+        let output = hash(OUTPUT);
+        self.state = hash(NEXT_STATE).to_vec();
+        output
+            .chunks(8)
+            .map(|chunk| {
+                let mut limb = [0; 8];
+                limb.copy_from_slice(chunk);
+                u64::from_le_bytes(limb)
+            })
+            .collect()
+    }
+
+    pub fn squeeze_limbs(&mut self, num_limbs: usize) -> Vec<u64> {
+        while self.last_squeezed.len() < num_limbs {
+            let limbs = self.squeeze();
+            self.last_squeezed.extend(limbs);
+        }
+        self.last_squeezed.drain(0..num_limbs).collect()
+    }
+
+    // This function squeezes a field element from fresh limbs, keeping its bits below the
+    // capacity of the field
+    pub fn squeeze_field<F: PrimeField>(&mut self) -> F {
+        self.last_squeezed = vec![];
+        let mut repr = F::BigInt::default();
+        let limbs = self.squeeze_limbs(repr.as_ref().len());
+        let capacity = F::Params::CAPACITY as usize;
+        for (i, (r, l)) in repr.as_mut().iter_mut().zip(limbs.iter()).enumerate() {
+            *r = match capacity.saturating_sub(64 * i) {
+                0 => 0,
+                bits if bits >= 64 => *l,
+                bits => *l & ((1 << bits) - 1),
+            };
+        }
+        F::from_repr(repr)
+    }
+
+    pub fn squeeze_challenge<F: PrimeField>(&mut self) -> F {
+        let mut repr = F::BigInt::default();
+        for (r, l) in repr
+            .as_mut()
+            .iter_mut()
+            .zip(self.squeeze_limbs(CHALLENGE_LENGTH_IN_LIMBS).iter())
+        {
+            *r = *l;
+        }
+        F::from_repr(repr)
+    }
+}
+
+#[derive(Clone)]
+pub struct KeccakFqSponge<P: SWModelParameters> {
+    pub sponge: KeccakSponge,
+    curve: PhantomData<P>,
+}
+
+pub struct KeccakFrSponge<Fr: Field> {
+    pub sponge: KeccakSponge,
+    field: PhantomData<Fr>,
+}
+
+impl<Fr: PrimeField> KeccakFrSponge<Fr> {
+    // the sponge is created with no parameters, those of Poseidon the FrSponge trait passes being
+    // ignored
+    pub fn create() -> Self {
+        KeccakFrSponge {
+            sponge: KeccakSponge::default(),
+            field: PhantomData,
+        }
+    }
+
+    pub fn absorb_scalars(&mut self, x: &[Fr]) {
+        x.iter().for_each(|x| self.sponge.absorb(&field_bytes(x)));
+    }
+
+    pub fn squeeze_challenge(&mut self) -> ScalarChallenge<Fr> {
+        ScalarChallenge(self.sponge.squeeze_challenge())
+    }
+
+    // This function absorbs the evaluations of a proof, the evaluation points and then the
+    // evaluations of each polynomial, for the FrSponge traits of the plonk crates
+    //     p: evaluation points
+    //     evals: evaluations of the polynomials, in the order of the absorption
+    pub fn absorb_proof_evaluations(&mut self, p: &[Fr], evals: &[&Vec<Fr>]) {
+        self.absorb_scalars(p);
+        for e in evals.iter() {
+            self.absorb_scalars(e);
+        }
+    }
+}
+
+impl<P: SWModelParameters> FqSponge<P::BaseField, GroupAffine<P>, P::ScalarField>
+    for KeccakFqSponge<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn new(_params: ArithmeticSpongeParams<P::BaseField>) -> KeccakFqSponge<P> {
+        KeccakFqSponge {
+            sponge: KeccakSponge::default(),
+            curve: PhantomData,
+        }
+    }
+
+    fn absorb_g(&mut self, g: &[GroupAffine<P>]) {
+        g.iter().for_each(|g| self.sponge.absorb(&point_bytes(g)));
+    }
+
+    fn absorb_fr(&mut self, x: &[P::ScalarField]) {
+        x.iter().for_each(|x| self.sponge.absorb(&field_bytes(x)));
+    }
+
+    fn digest(mut self) -> P::ScalarField {
+        self.sponge.squeeze_field()
+    }
+
+    fn challenge(&mut self) -> P::ScalarField {
+        self.sponge.squeeze_challenge()
+    }
+
+    fn challenge_fq(&mut self) -> P::BaseField {
+        self.sponge.squeeze_field()
+    }
+}
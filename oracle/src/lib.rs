@@ -1,13 +1,19 @@
+pub mod keccak;
 pub mod pasta;
 pub mod poseidon;
+pub mod poseidon_params;
 pub mod rndoracle;
+pub mod serialization;
 pub mod sponge;
+pub mod transcript;
 pub mod utils;
 
 use algebra::Field;
 
 pub trait FqSponge<Fq: Field, G, Fr> {
     fn new(p: poseidon::ArithmeticSpongeParams<Fq>) -> Self;
+    // This function labels the step of the argument, for the transcripts that record it
+    fn label(&mut self, _label: &'static str) {}
     fn absorb_g(&mut self, g: &[G]);
     fn absorb_fr(&mut self, x: &[Fr]);
     fn challenge(&mut self) -> Fr;
@@ -2,9 +2,21 @@
 
 This file implements Poseidon Hash Function primitive
 
+The hash function of variable-length inputs is built on the sponge as follows
+
+1. the capacity of the initial state holds the domain tag, so that the hash functions of
+    distinct domains are independent
+2. the input is padded with a one and as few zeros as fill the last block, which is injective
+3. the blocks are added to the rate of the state, the state being permuted between the blocks
+    and before squeezing
+
+A circuit computes the same hash with constant initial state, additions of the blocks and one
+Poseidon gate per full round of each permutation. Byte strings are packed into field elements,
+with the same padding, as many bytes per element as fit below the modulus.
+
 *****************************************************************************************************************/
 
-use algebra::Field;
+use algebra::{Field, FpParameters, PrimeField};
 
 pub trait SpongeConstants {
     const ROUNDS_FULL: usize;
@@ -258,3 +270,74 @@ impl<F: Field, SC: SpongeConstants> Sponge<F, F> for ArithmeticSponge<F, SC> {
         }
     }
 }
+
+// This function hashes a variable-length input, in the domain of the tag
+//     params: parameters of the sponge
+//     domain_tag: tag of the domain of the hash function, see domain_tag
+//     input: field elements to hash
+//     RETURN: hash of the input
+pub fn hash<F: Field, SC: SpongeConstants>(
+    params: ArithmeticSpongeParams<F>,
+    domain_tag: F,
+    input: &[F],
+) -> F {
+    let mut sponge = ArithmeticSponge::<F, SC>::new(params);
+    sponge.state[SC::SPONGE_RATE] = domain_tag;
+
+    let mut padded = input.to_vec();
+    padded.push(F::one());
+    let len = padded.len();
+    padded.resize(
+        len + (SC::SPONGE_RATE - len % SC::SPONGE_RATE) % SC::SPONGE_RATE,
+        F::zero(),
+    );
+
+    sponge.absorb(&padded);
+    sponge.squeeze()
+}
+
+// This function hashes a byte string, in the domain of the tag
+pub fn hash_bytes<F: PrimeField, SC: SpongeConstants>(
+    params: ArithmeticSpongeParams<F>,
+    domain_tag: F,
+    input: &[u8],
+) -> F {
+    hash::<F, SC>(params, domain_tag, &pack_bytes(input))
+}
+
+// This function returns the tag of a domain, the name of the domain packed into a single
+// field element
+//     name: name of the domain, shorter than the bytes an element packs
+pub fn domain_tag<F: PrimeField>(name: &str) -> F {
+    let packed = pack_bytes::<F>(name.as_bytes());
+    assert!(packed.len() == 1, "domain name {} too long", name);
+    packed[0]
+}
+
+// This function returns the number of bytes packed into a field element, the bytes whose
+// integers are all below the modulus
+pub fn bytes_per_element<F: PrimeField>() -> usize {
+    F::Params::CAPACITY as usize / 8
+}
+
+// This function packs a byte string into field elements, padded with a one byte and as few
+// zero bytes as fill the last element, each element holding the little-endian integer of
+// its bytes
+pub fn pack_bytes<F: PrimeField>(bytes: &[u8]) -> Vec<F> {
+    let n = bytes_per_element::<F>();
+    let mut padded = bytes.to_vec();
+    padded.push(1);
+    let len = padded.len();
+    padded.resize(len + (n - len % n) % n, 0);
+
+    padded
+        .chunks(n)
+        .map(|chunk| {
+            let mut repr = F::BigInt::default();
+            for (limb, bytes) in repr.as_mut().iter_mut().zip(chunk.chunks(8)) {
+                *limb = bytes.iter().rev().fold(0, |acc, b| (acc << 8) | *b as u64);
+            }
+            F::from_repr(repr)
+        })
+        .collect()
+}
@@ -0,0 +1,304 @@
+/*****************************************************************************************************************
+
+This file implements the derivation of the Poseidon parameters, as params.sage does
+
+The round constants and the MDS matrix are derived from a parameter set name, so that every
+definition of a hash function has its own parameters:
+
+1. the round constants are uniform field elements, by rejection sampling of the SHA-256 digests
+    of their prefixed indices
+2. the MDS matrix is the Cauchy matrix 1 / (x_i - y_j) of such uniform elements, the first
+    attempt with no eigenvalue in the field being chosen, its determinant being checked against
+    the closed form of the Cauchy determinants, which is nonzero
+
+Legacy parameter sets, the original 3- and 5-wire ones, are derived with the prefixes they were
+generated with before the parameter sets were named.
+
+*****************************************************************************************************************/
+
+use crate::poseidon::{ArithmeticSpongeParams, SpongeConstants};
+use algebra::{FpParameters, PrimeField};
+use sha2::{Digest, Sha256};
+
+const PREFIX: &str = "CodaRescue";
+const MDS_ATTEMPTS: usize = 100;
+
+// The name of a parameter set
+#[derive(Clone, Copy, Debug)]
+pub enum Instance<'a> {
+    // the original parameter set of the width, of width 3 or 5 only
+    Legacy,
+    // a named parameter set
+    Named(&'a str),
+}
+
+// This function returns the number of round constants the sponge uses
+pub fn rounds<SC: SpongeConstants>() -> usize {
+    SC::ROUNDS_FULL + SC::ROUNDS_PARTIAL + if SC::INITIAL_ARK { 1 } else { 0 }
+}
+
+// This function derives the parameters of the sponge, with as many round constants as it uses
+//     field: label of the field, e.g. Pasta_p
+//     instance: name of the parameter set
+//     RETURN: round constants and MDS matrix
+pub fn params<F: PrimeField, SC: SpongeConstants>(
+    field: &str,
+    instance: Instance,
+) -> ArithmeticSpongeParams<F> {
+    generate::<F>(field, instance, SC::SPONGE_WIDTH, rounds::<SC>())
+}
+
+// This function derives the parameters of the sponge, as params.sage does
+//     field: label of the field, e.g. Pasta_p
+//     instance: name of the parameter set
+//     width: width of the sponge
+//     rounds: number of round constants
+//     RETURN: round constants and MDS matrix
+pub fn generate<F: PrimeField>(
+    field: &str,
+    instance: Instance,
+    width: usize,
+    rounds: usize,
+) -> ArithmeticSpongeParams<F> {
+    let (round_prefix, mds_prefix) = match instance {
+        Instance::Legacy => {
+            let suffix = match width {
+                3 => "",
+                5 => "5",
+                _ => panic!("no legacy Poseidon parameters of width {}", width),
+            };
+            (
+                format!("{}{}RoundConstants", field, suffix),
+                format!("{}MDS", PREFIX),
+            )
+        }
+        Instance::Named(name) => (
+            format!("{}{}_{}RoundConstants", PREFIX, field, name),
+            format!("{}{}_{}MDS", PREFIX, field, name),
+        ),
+    };
+
+    ArithmeticSpongeParams {
+        round_constants: (0..rounds)
+            .map(|r| {
+                (0..width)
+                    .map(|i| random_value(&round_prefix, r * width + i))
+                    .collect()
+            })
+            .collect(),
+        mds: mds(&mds_prefix, width)
+            .unwrap_or_else(|| panic!("no secure MDS matrix for {}", mds_prefix)),
+    }
+}
+
+// This function derives a uniform field element from the SHA-256 digests of the prefixed index,
+// rejecting the digests past the modulus
+fn random_value<F: PrimeField>(prefix: &str, i: usize) -> F {
+    (0..)
+        .filter_map(|j| {
+            let digest = Sha256::digest(format!("{}{}_{}", prefix, i, j).as_bytes());
+            of_be_bytes::<F>(&digest)
+        })
+        .next()
+        .unwrap()
+}
+
+// This function converts a big-endian integer to a field element if below the modulus
+fn of_be_bytes<F: PrimeField>(bytes: &[u8]) -> Option<F> {
+    let mut repr = F::BigInt::default();
+    let limbs = repr.as_ref().len();
+    assert!(bytes.len() <= 8 * limbs);
+    for (limb, chunk) in repr.as_mut().iter_mut().zip(bytes.rchunks(8)) {
+        *limb = chunk.iter().fold(0, |acc, b| (acc << 8) | *b as u64);
+    }
+    if repr < F::Params::MODULUS {
+        Some(F::from_repr(repr))
+    } else {
+        None
+    }
+}
+
+// This function derives the MDS matrix, the first Cauchy matrix with no eigenvalue in the field
+fn mds<F: PrimeField>(prefix: &str, width: usize) -> Option<Vec<Vec<F>>> {
+    (0..MDS_ATTEMPTS).find_map(|attempt| {
+        let values = |axis: &str| -> Vec<F> {
+            (0..width)
+                .map(|i| random_value(&format!("{}{}", prefix, axis), attempt * width + i))
+                .collect()
+        };
+        let (x, y) = (values("x"), values("y"));
+        let distinct = x
+            .iter()
+            .chain(y.iter())
+            .enumerate()
+            .all(|(i, a)| x.iter().chain(y.iter()).skip(i + 1).all(|b| a != b));
+        assert!(distinct, "the values of x and y are not distinct");
+
+        let m: Vec<Vec<F>> = x
+            .iter()
+            .map(|x_i| {
+                y.iter()
+                    .map(|y_j| (*x_i - y_j).inverse().unwrap())
+                    .collect()
+            })
+            .collect();
+        check_cauchy_determinant(&m, &x, &y);
+        if is_secure_mds(&m) {
+            Some(m)
+        } else {
+            None
+        }
+    })
+}
+
+// This function checks the determinant of the Cauchy matrix 1 / (x_i - y_j) against its
+// closed form, the product of the (x_i - x_j) (y_i - y_j) for j < i over the product of the
+// (x_i - y_j), up to the sign of the reversal of the rows, panicking as params.sage asserts
+fn check_cauchy_determinant<F: PrimeField>(m: &[Vec<F>], x: &[F], y: &[F]) {
+    let n = x.len();
+    let mut num = F::one();
+    let mut den = F::one();
+    for i in 0..n {
+        for j in 0..i {
+            num *= &((x[i] - &x[j]) * &(y[i] - &y[j]));
+        }
+        for j in 0..n {
+            den *= &(x[i] - &y[j]);
+        }
+    }
+    let expected = num * &den.inverse().unwrap();
+    let expected = if n % 4 < 2 { expected } else { -expected };
+
+    let det = determinant(m);
+    assert!(!det.is_zero(), "the MDS matrix is not invertible");
+    assert!(
+        det == expected,
+        "expected determinant {:?}, found {:?}",
+        expected,
+        det
+    );
+}
+
+// This function computes the determinant of the matrix by Gaussian elimination
+pub fn determinant<F: PrimeField>(m: &[Vec<F>]) -> F {
+    let n = m.len();
+    let mut a = m.to_vec();
+    let mut det = F::one();
+    for k in 0..n {
+        let pivot = match (k..n).find(|i| !a[*i][k].is_zero()) {
+            Some(pivot) => pivot,
+            None => return F::zero(),
+        };
+        if pivot != k {
+            a.swap(pivot, k);
+            det = -det;
+        }
+        det *= &a[k][k];
+        let inv = a[k][k].inverse().unwrap();
+        for i in k + 1..n {
+            let scale = a[i][k] * &inv;
+            for j in k..n {
+                let d = scale * &a[k][j];
+                a[i][j] -= &d;
+            }
+        }
+    }
+    det
+}
+
+// This function checks the MDS matrix to have no eigenvalue in the field, that is its
+// characteristic polynomial to have no root, which rules out its invariant subspaces of
+// dimension one
+pub fn is_secure_mds<F: PrimeField>(m: &[Vec<F>]) -> bool {
+    let f = characteristic_polynomial(m);
+    // the roots of f in the field are the roots of gcd(f, X^p - X)
+    let mut g = pow_mod(&[F::zero(), F::one()], F::Params::MODULUS.as_ref(), &f);
+    g.resize(std::cmp::max(g.len(), 2), F::zero());
+    g[1] -= &F::one();
+    degree(&gcd(f, g)) == Some(0)
+}
+
+// This function computes the characteristic polynomial det(X I - M) by the Faddeev-LeVerrier
+// algorithm, the coefficients being in increasing degree order
+fn characteristic_polynomial<F: PrimeField>(m: &[Vec<F>]) -> Vec<F> {
+    let n = m.len();
+    let product = |a: &[Vec<F>], b: &[Vec<F>]| -> Vec<Vec<F>> {
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| (0..n).fold(F::zero(), |acc, k| acc + &(a[i][k] * &b[k][j])))
+                    .collect()
+            })
+            .collect()
+    };
+
+    let mut c = vec![F::zero(); n + 1];
+    c[n] = F::one();
+    // M_k = M M_{k-1} + c_{n-k+1} I, c_{n-k} = -tr(M M_k) / k
+    let mut mk = vec![vec![F::zero(); n]; n];
+    for k in 1..=n {
+        mk = product(m, &mk);
+        for (i, row) in mk.iter_mut().enumerate() {
+            row[i] += &c[n - k + 1];
+        }
+        let mmk = product(m, &mk);
+        let trace = (0..n).fold(F::zero(), |acc, i| acc + &mmk[i][i]);
+        c[n - k] = -(trace * &F::from(k as u64).inverse().unwrap());
+    }
+    c
+}
+
+// The degree of the polynomial, none for the zero polynomial
+fn degree<F: PrimeField>(p: &[F]) -> Option<usize> {
+    p.iter().rposition(|c| !c.is_zero())
+}
+
+// This function computes the remainder of the division of p by the nonzero polynomial f
+fn rem<F: PrimeField>(mut p: Vec<F>, f: &[F]) -> Vec<F> {
+    let d = degree(f).unwrap();
+    let lead_inv = f[d].inverse().unwrap();
+    while let Some(dp) = degree(&p) {
+        if dp < d {
+            break;
+        }
+        let scale = p[dp] * &lead_inv;
+        for (i, c) in f[0..=d].iter().enumerate() {
+            p[dp - d + i] -= &(scale * c);
+        }
+    }
+    p.truncate(d);
+    p
+}
+
+fn mul_mod<F: PrimeField>(a: &[F], b: &[F], f: &[F]) -> Vec<F> {
+    let mut p = vec![F::zero(); a.len() + b.len()];
+    for (i, a_i) in a.iter().enumerate() {
+        for (j, b_j) in b.iter().enumerate() {
+            p[i + j] += &(*a_i * b_j);
+        }
+    }
+    rem(p, f)
+}
+
+// This function computes p^e mod f, the exponent being given by its little-endian limbs
+fn pow_mod<F: PrimeField>(p: &[F], e: &[u64], f: &[F]) -> Vec<F> {
+    let mut res = rem(vec![F::one()], f);
+    for limb in e.iter().rev() {
+        for bit in (0..64).rev() {
+            res = mul_mod(&res, &res, f);
+            if (limb >> bit) & 1 == 1 {
+                res = mul_mod(&res, p, f);
+            }
+        }
+    }
+    res
+}
+
+fn gcd<F: PrimeField>(mut a: Vec<F>, mut b: Vec<F>) -> Vec<F> {
+    while degree(&b).is_some() {
+        let r = rem(a, &b);
+        a = b;
+        b = r;
+    }
+    a
+}
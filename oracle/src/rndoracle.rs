@@ -18,11 +18,17 @@ pub enum ProofError {
     ProofCreation,
     ProofVerification,
     OpenProof,
+    IncompatibleSrs,
     SumCheck,
     ConstraintInconsist,
     EvaluationGroup,
     OracleCommit,
     RuntimeEnv,
+    PrevChallenges,
+    InvalidPoint(InvalidPoint),
+    // a commitment or evaluation vector of the proof is missing or of unexpected length,
+    // with the location of the field
+    ProofShape(&'static str),
 }
 
 // Implement `Display` for ProofError
@@ -31,3 +37,36 @@ impl fmt::Display for ProofError {
         write!(f, "({})", self)
     }
 }
+
+// A malformed point of untrusted or deserialized data, with the location of the point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPoint {
+    // the point is not on the curve or not in its prime order subgroup
+    NotOnCurve(&'static str),
+    // the point is the identity where a non-trivial point is expected
+    Identity(&'static str),
+}
+
+impl fmt::Display for InvalidPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidPoint::NotOnCurve(location) => write!(f, "point not on the curve: {}", location),
+            InvalidPoint::Identity(location) => write!(f, "identity point: {}", location),
+        }
+    }
+}
+
+impl std::error::Error for InvalidPoint {}
+
+// The check of the verification of a proof that fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedCheck {
+    // the validation of the points and of the previous challenges of the proof
+    Validation,
+    // the linearization/quotient identity at the evaluation point
+    Linearization,
+    // the inner product argument equation of the opening proof
+    Ipa,
+    // the consistency of the sg component of the opening proof with its challenges
+    Sg,
+}
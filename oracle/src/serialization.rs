@@ -0,0 +1,44 @@
+/*****************************************************************************************************************
+
+This source file implements the encoding of the vectors of the binary encoding of proofs,
+shared by the circuits and the commitment crates: the vectors are prefixed with their length
+as u64
+
+As the lengths are read from untrusted input, the memory reserved upfront for a vector is
+capped, the vector growing past the cap only as its elements are actually read
+
+*****************************************************************************************************************/
+
+use algebra::{FromBytes, ToBytes};
+use std::cmp::min;
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind, Read, Result as IoResult, Write};
+
+// maximal number of elements reserved upfront for a vector read
+pub const MAX_PREALLOC: usize = 1 << 16;
+
+pub fn write_len<W: Write>(len: usize, writer: W) -> IoResult<()> {
+    (len as u64).write(writer)
+}
+
+pub fn read_len<R: Read>(reader: R) -> IoResult<usize> {
+    usize::try_from(u64::read(reader)?)
+        .map_err(|_| Error::new(ErrorKind::InvalidData, "Length out of range"))
+}
+
+pub fn write_fields<F: ToBytes, W: Write>(xs: &[F], mut writer: W) -> IoResult<()> {
+    write_len(xs.len(), &mut writer)?;
+    for x in xs.iter() {
+        x.write(&mut writer)?;
+    }
+    Ok(())
+}
+
+pub fn read_fields<F: FromBytes, R: Read>(mut reader: R) -> IoResult<Vec<F>> {
+    let n = read_len(&mut reader)?;
+    let mut xs = Vec::with_capacity(min(n, MAX_PREALLOC));
+    for _ in 0..n {
+        xs.push(F::read(&mut reader)?);
+    }
+    Ok(xs)
+}
@@ -141,8 +141,11 @@ where
     fn absorb_g(&mut self, g: &[GroupAffine<P>]) {
         self.last_squeezed = vec![];
         for g in g.iter() {
+            // the point at infinity is absorbed as (0, 0), which is not on the curves
+            // y^2 = x^3 + ax + b with b nonzero, so that the encoding is injective
             if g.infinity {
-                panic!("sponge got zero curve point");
+                self.sponge.absorb(&[P::BaseField::zero()]);
+                self.sponge.absorb(&[P::BaseField::zero()]);
             } else {
                 self.sponge.absorb(&[g.x]);
                 self.sponge.absorb(&[g.y]);
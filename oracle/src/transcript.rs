@@ -0,0 +1,199 @@
+/*****************************************************************************************************************
+
+This source file implements the labelled transcript of the random oracle arguments
+
+Transcript wraps a sponge and, while recording, logs every absorption and every squeeze of the
+sponge with the label of the step of the argument. The label is set by the prover and the
+verifier before each step, which the sponges themselves ignore.
+
+The traces of the prover and of the verifier of a proof are the same, the first event where
+they differ pinpointing the step where the verifier does not follow the prover. Recording is
+per thread, the sponges of the recorded code running on the recording thread.
+
+*****************************************************************************************************************/
+
+use crate::poseidon::ArithmeticSpongeParams;
+use crate::FqSponge;
+use algebra::Field;
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Display};
+
+thread_local! {
+    static RECORDING: RefCell<Option<Vec<Event>>> = RefCell::new(None);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    Absorb,
+    Squeeze,
+}
+
+// An absorption or a squeeze of a sponge, with its values
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub label: &'static str,
+    pub kind: EventKind,
+    pub values: Vec<String>,
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            EventKind::Absorb => "absorb",
+            EventKind::Squeeze => "squeeze",
+        };
+        write!(f, "{} {} [{}]", self.label, kind, self.values.join(", "))
+    }
+}
+
+// The events of the sponges, in order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace(pub Vec<Event>);
+
+impl Trace {
+    // This function returns the index of the first event where the traces differ, if any,
+    // the end of the shorter trace being an event of its own
+    pub fn first_divergence(&self, other: &Trace) -> Option<usize> {
+        let (a, b) = (&self.0, &other.0);
+        match a.iter().zip(b.iter()).position(|(x, y)| x != y) {
+            Some(i) => Some(i),
+            None if a.len() != b.len() => Some(std::cmp::min(a.len(), b.len())),
+            None => None,
+        }
+    }
+
+    // This function dumps the traces side by side, one event per line, up to the first
+    // divergence and the event that follows it, the divergence being marked
+    //     other: trace to compare to, e.g. the verifier's one against the prover's one
+    //     RETURN: the lines of the dump
+    pub fn side_by_side(&self, other: &Trace) -> String {
+        let divergence = self.first_divergence(other);
+        let end = match divergence {
+            Some(i) => i + 2,
+            None => self.0.len(),
+        };
+        let show = |t: &Trace, i: usize| match t.0.get(i) {
+            Some(e) => e.to_string(),
+            None => "<end>".to_string(),
+        };
+        (0..end)
+            .filter(|i| *i < std::cmp::max(self.0.len(), other.0.len()))
+            .map(|i| {
+                let mark = if Some(i) == divergence { ">>" } else { "  " };
+                format!(
+                    "{} {:4} | {} | {}\n",
+                    mark,
+                    i,
+                    show(self, i),
+                    show(other, i)
+                )
+            })
+            .collect()
+    }
+}
+
+// This function runs the code, recording the events of the transcripts on this thread
+//     f: code to run, e.g. the prover or the verifier
+//     RETURN: the result of the code and the trace of its transcripts
+pub fn record<R, F: FnOnce() -> R>(f: F) -> (R, Trace) {
+    RECORDING.with(|r| *r.borrow_mut() = Some(vec![]));
+    let res = f();
+    let events = RECORDING
+        .with(|r| r.borrow_mut().take())
+        .unwrap_or_default();
+    (res, Trace(events))
+}
+
+// A sponge logging its events while recording
+#[derive(Clone)]
+pub struct Transcript<S> {
+    pub sponge: S,
+    label: &'static str,
+}
+
+impl<S> Transcript<S> {
+    pub fn wrap(sponge: S) -> Self {
+        Transcript { sponge, label: "" }
+    }
+
+    // This function sets the label of the events that follow
+    pub fn set_label(&mut self, label: &'static str) {
+        self.label = label;
+    }
+
+    pub fn absorbed<T: Debug>(&self, values: &[T]) {
+        self.log(EventKind::Absorb, values)
+    }
+
+    pub fn squeezed<T: Debug>(&self, value: &T) {
+        self.log(EventKind::Squeeze, &[value])
+    }
+
+    // This function logs the absorption of the evaluations of a proof, the evaluation points
+    // and then the evaluations of each polynomial, one event each, as the default sponge
+    // absorbs them
+    //     p: evaluation points
+    //     evals: evaluations of the polynomials, in the order of the absorption
+    pub fn absorbed_evaluations<T: Debug>(&self, p: &[T], evals: &[&Vec<T>]) {
+        self.absorbed(p);
+        for e in evals.iter() {
+            self.absorbed(e);
+        }
+    }
+
+    fn log<T: Debug>(&self, kind: EventKind, values: &[T]) {
+        log(self.label, kind, values)
+    }
+}
+
+// This function logs an event if this thread is recording
+fn log<T: Debug>(label: &'static str, kind: EventKind, values: &[T]) {
+    RECORDING.with(|r| {
+        if let Some(events) = r.borrow_mut().as_mut() {
+            events.push(Event {
+                label,
+                kind,
+                values: values.iter().map(|v| format!("{:?}", v)).collect(),
+            })
+        }
+    })
+}
+
+impl<Fq: Field, G: Debug, Fr: Debug, S: FqSponge<Fq, G, Fr>> FqSponge<Fq, G, Fr> for Transcript<S> {
+    fn new(p: ArithmeticSpongeParams<Fq>) -> Self {
+        Transcript::wrap(S::new(p))
+    }
+
+    fn label(&mut self, label: &'static str) {
+        self.set_label(label)
+    }
+
+    fn absorb_g(&mut self, g: &[G]) {
+        self.absorbed(g);
+        self.sponge.absorb_g(g)
+    }
+
+    fn absorb_fr(&mut self, x: &[Fr]) {
+        self.absorbed(x);
+        self.sponge.absorb_fr(x)
+    }
+
+    fn challenge(&mut self) -> Fr {
+        let c = self.sponge.challenge();
+        self.squeezed(&c);
+        c
+    }
+
+    fn challenge_fq(&mut self) -> Fq {
+        let c = self.sponge.challenge_fq();
+        self.squeezed(&c);
+        c
+    }
+
+    fn digest(self) -> Fr {
+        let label = self.label;
+        let d = self.sponge.digest();
+        log(label, EventKind::Squeeze, &[&d]);
+        d
+    }
+}
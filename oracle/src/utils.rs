@@ -82,7 +82,11 @@ impl<F: FftField> PolyUtils<F> for DensePolynomial<F> {
     }
 
     // This function evaluates polynomial in chunks
+    // The zero polynomial has a single zero chunk, matching its hiding commitment
     fn eval(&self, elm: F, size: usize) -> Vec<F> {
+        if self.coeffs.is_empty() {
+            return vec![F::zero()];
+        }
         (0..self.coeffs.len())
             .step_by(size)
             .map(|i| {
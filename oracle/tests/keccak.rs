@@ -0,0 +1,76 @@
+use algebra::{AffineCurve, Field, One, PrimeField, UniformRand};
+use mina_curves::pasta::{
+    vesta::{Affine, VestaParameters},
+    Fp, Fq,
+};
+use oracle::keccak::{field_bytes, point_bytes, KeccakFqSponge, KeccakSponge};
+use oracle::FqSponge;
+
+fn sponge() -> KeccakFqSponge<VestaParameters> {
+    KeccakFqSponge::new(oracle::pasta::fq::params())
+}
+
+#[test]
+fn keccak_encoding() {
+    let mut one = vec![0; 32];
+    one[31] = 1;
+    assert_eq!(field_bytes(&Fp::one()), one);
+    assert_eq!(field_bytes(&Fp::from(0x0102 as u64))[30..], [1, 2]);
+
+    // the largest element, p - 1, in big-endian bytes
+    let minus_one = field_bytes(&-Fp::one());
+    let repr = (-Fp::one()).into_repr();
+    let limbs: Vec<u8> = repr
+        .as_ref()
+        .iter()
+        .rev()
+        .flat_map(|l| l.to_be_bytes().to_vec())
+        .collect();
+    assert_eq!(minus_one, limbs);
+
+    let g = Affine::prime_subgroup_generator();
+    assert_eq!(
+        point_bytes(&g),
+        [field_bytes(&g.x), field_bytes(&g.y)].concat()
+    );
+}
+
+#[test]
+fn keccak_squeeze() {
+    // the output of the empty state is Keccak-256 of the 0 byte
+    let mut s = KeccakSponge::default();
+    let limbs = s.squeeze();
+    assert_eq!(
+        limbs[0].to_le_bytes(),
+        [0xbc, 0x36, 0x78, 0x9e, 0x7a, 0x1e, 0x28, 0x14]
+    );
+
+    let rng = &mut rand::thread_rng();
+    let x = Fp::rand(rng);
+    let g = Affine::prime_subgroup_generator();
+
+    // the sponges absorbing the same values squeeze the same challenges
+    let mut a = sponge();
+    let mut b = sponge();
+    a.absorb_fr(&[x]);
+    a.absorb_g(&[g]);
+    b.absorb_fr(&[x]);
+    b.absorb_g(&[g]);
+    assert_eq!(a.challenge(), b.challenge());
+    assert_eq!(a.challenge_fq(), b.challenge_fq());
+
+    // the challenges are of 128 bits, the squeezed field elements below the capacity
+    for _ in 0..16 {
+        let c = a.challenge().into_repr();
+        assert_eq!(c.as_ref()[2..], [0, 0]);
+        let f = a.challenge_fq();
+        assert!(f < Fq::from(2 as u64).pow([254]));
+    }
+
+    // and the absorbed values change them
+    let mut c = sponge();
+    c.absorb_fr(&[x + &Fp::one()]);
+    c.absorb_g(&[g]);
+    assert_ne!(b.challenge(), c.challenge());
+    assert_ne!(b.digest(), c.digest());
+}
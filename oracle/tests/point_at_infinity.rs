@@ -0,0 +1,46 @@
+use algebra::{AffineCurve, UniformRand, Zero};
+use mina_curves::pasta::{
+    vesta::{Affine, VestaParameters},
+    Fp, Fq,
+};
+use oracle::{
+    keccak::{point_bytes, KeccakFqSponge},
+    poseidon::PlonkSpongeConstants as SC,
+    sponge::DefaultFqSponge,
+    FqSponge,
+};
+
+// This function squeezes the challenge of the sponge absorbing the points
+fn challenge<S: FqSponge<Fq, Affine, Fp>>(points: &[Affine]) -> Fp {
+    let mut sponge = S::new(oracle::pasta::fq::params());
+    sponge.absorb_g(points);
+    sponge.challenge()
+}
+
+fn point_at_infinity<S: FqSponge<Fq, Affine, Fp>>() {
+    let rng = &mut rand::thread_rng();
+    let g = Affine::prime_subgroup_generator();
+    let zero = Affine::zero();
+
+    // the identity is absorbed as (0, 0), which is not on the curve
+    let origin = Affine::new(Fq::zero(), Fq::zero(), false);
+    assert!(!origin.is_on_curve());
+    assert_eq!(challenge::<S>(&[zero]), challenge::<S>(&[origin]));
+
+    // and apart from the points of the curve
+    let p: Affine = g.mul(Fp::rand(rng)).into();
+    assert_ne!(challenge::<S>(&[zero]), challenge::<S>(&[p]));
+    assert_ne!(challenge::<S>(&[zero, g]), challenge::<S>(&[g, zero]));
+    assert_ne!(challenge::<S>(&[zero]), challenge::<S>(&[zero, zero]));
+}
+
+#[test]
+fn poseidon_point_at_infinity() {
+    point_at_infinity::<DefaultFqSponge<VestaParameters, SC>>();
+}
+
+#[test]
+fn keccak_point_at_infinity() {
+    point_at_infinity::<KeccakFqSponge<VestaParameters>>();
+    assert_eq!(point_bytes(&Affine::zero()), vec![0; 64]);
+}
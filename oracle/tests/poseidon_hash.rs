@@ -0,0 +1,87 @@
+use algebra::{Field, One, UniformRand, Zero};
+use mina_curves::pasta::Fp;
+use oracle::pasta::fp5;
+use oracle::poseidon::{
+    bytes_per_element, domain_tag, hash, hash_bytes, pack_bytes, ArithmeticSponge,
+    PlonkSpongeConstants5W as SC, Sponge, SpongeConstants,
+};
+
+fn hash5(tag: Fp, input: &[Fp]) -> Fp {
+    hash::<Fp, SC>(fp5::params(), tag, input)
+}
+
+#[test]
+fn hash_padding() {
+    let rng = &mut rand::thread_rng();
+    let tag = domain_tag::<Fp>("test");
+    let a = Fp::rand(rng);
+
+    // the inputs differing by trailing zeros are hashed apart
+    assert_ne!(hash5(tag, &[a]), hash5(tag, &[a, Fp::zero()]));
+    assert_ne!(hash5(tag, &[]), hash5(tag, &[Fp::zero()]));
+    let block: Vec<Fp> = (0..SC::SPONGE_RATE).map(|_| Fp::rand(rng)).collect();
+    assert_ne!(
+        hash5(tag, &block),
+        hash5(tag, &[&block[..], &[Fp::one()]].concat())
+    );
+
+    // and so are the domains
+    assert_ne!(hash5(tag, &[a]), hash5(domain_tag("other"), &[a]));
+    assert_ne!(hash5(tag, &[a]), hash5(Fp::zero(), &[a]));
+}
+
+#[test]
+fn hash_gadget() {
+    let rng = &mut rand::thread_rng();
+    let tag = domain_tag::<Fp>("gadget");
+    let input: Vec<Fp> = (0..2 * SC::SPONGE_RATE + 1)
+        .map(|_| Fp::rand(rng))
+        .collect();
+
+    // the hash as a circuit computes it: the tag in the capacity, the padded blocks added to the
+    // rate and one Poseidon gate per full round of the permutation
+    let mut padded = input.clone();
+    padded.push(Fp::one());
+    padded.resize(3 * SC::SPONGE_RATE, Fp::zero());
+    let mut rows = ArithmeticSponge::<Fp, SC>::new(fp5::params());
+    rows.state[SC::SPONGE_RATE] = tag;
+    for block in padded.chunks(SC::SPONGE_RATE) {
+        for (s, x) in rows.state.iter_mut().zip(block.iter()) {
+            *s += x;
+        }
+        for r in 0..SC::ROUNDS_FULL {
+            rows.full_round(r);
+        }
+    }
+
+    assert_eq!(hash5(tag, &input), rows.state[0]);
+}
+
+#[test]
+fn byte_packing() {
+    let n = bytes_per_element::<Fp>();
+    assert_eq!(n, 31);
+
+    // the byte strings differing by trailing zeros are packed apart
+    assert_ne!(pack_bytes::<Fp>(b"a"), pack_bytes::<Fp>(b"a\0"));
+    assert_eq!(pack_bytes::<Fp>(b""), vec![Fp::one()]);
+    assert_eq!(pack_bytes::<Fp>(&vec![7; n - 1]).len(), 1);
+    assert_eq!(pack_bytes::<Fp>(&vec![7; n]).len(), 2);
+
+    // the bytes are packed little-endian
+    assert_eq!(pack_bytes::<Fp>(&[2]), vec![Fp::from(256 + 2 as u64)]);
+    assert_eq!(
+        pack_bytes::<Fp>(&[0xff; 30])[0],
+        Fp::from(2 as u64).pow([241]) - Fp::one()
+    );
+
+    let tag = domain_tag::<Fp>("bytes");
+    assert_eq!(
+        hash_bytes::<Fp, SC>(fp5::params(), tag, b"abc"),
+        hash5(tag, &pack_bytes(b"abc"))
+    );
+    assert_ne!(
+        hash_bytes::<Fp, SC>(fp5::params(), tag, b"abc"),
+        hash_bytes::<Fp, SC>(fp5::params(), tag, b"abc\0")
+    );
+}
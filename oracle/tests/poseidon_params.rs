@@ -0,0 +1,111 @@
+use algebra::{One, Zero};
+use mina_curves::pasta::{Fp, Fq};
+use oracle::pasta::{fp, fp5, fp_3, fq, fq5, fq_3};
+use oracle::poseidon::{
+    ArithmeticSpongeParams, PlonkSpongeConstants, PlonkSpongeConstants3, PlonkSpongeConstants5W,
+};
+use oracle::poseidon_params::{determinant, generate, is_secure_mds, params, rounds, Instance};
+
+fn assert_params_eq<F: algebra::Field>(
+    a: &ArithmeticSpongeParams<F>,
+    b: &ArithmeticSpongeParams<F>,
+) {
+    assert_eq!(a.mds, b.mds);
+    assert_eq!(a.round_constants, b.round_constants);
+}
+
+#[test]
+fn legacy_params() {
+    // the legacy tables were generated with 100 round constants
+    assert_params_eq(
+        &generate::<Fp>("Pasta_p", Instance::Legacy, 3, 100),
+        &fp::params(),
+    );
+    assert_params_eq(
+        &generate::<Fq>("Pasta_q", Instance::Legacy, 3, 100),
+        &fq::params(),
+    );
+    assert_params_eq(
+        &generate::<Fp>("Pasta_p", Instance::Legacy, 5, 100),
+        &fp5::params(),
+    );
+    assert_params_eq(
+        &generate::<Fq>("Pasta_q", Instance::Legacy, 5, 100),
+        &fq5::params(),
+    );
+
+    // of which the sponges use a prefix
+    let p = params::<Fp, PlonkSpongeConstants>("Pasta_p", Instance::Legacy);
+    assert_eq!(p.round_constants.len(), rounds::<PlonkSpongeConstants>());
+    assert_eq!(p.round_constants[..], fp::params().round_constants[0..64]);
+    let p = params::<Fq, PlonkSpongeConstants5W>("Pasta_q", Instance::Legacy);
+    assert_eq!(p.round_constants[..], fq5::params().round_constants[0..53]);
+}
+
+#[test]
+fn named_params() {
+    assert_params_eq(
+        &params::<Fp, PlonkSpongeConstants3>("Pasta_p", Instance::Named("3")),
+        &fp_3::params(),
+    );
+    assert_params_eq(
+        &params::<Fq, PlonkSpongeConstants3>("Pasta_q", Instance::Named("3")),
+        &fq_3::params(),
+    );
+
+    // another name gives other parameters
+    let other = params::<Fp, PlonkSpongeConstants3>("Pasta_p", Instance::Named("3x"));
+    assert_ne!(other.mds, fp_3::params().mds);
+    assert_ne!(other.round_constants, fp_3::params().round_constants);
+}
+
+#[test]
+fn mds_security() {
+    for m in [fp::params().mds, fp5::params().mds, fp_3::params().mds].iter() {
+        assert!(is_secure_mds(m));
+    }
+
+    // the matrices with an eigenvalue in the field are rejected, as a rotation fixing a vector
+    let mut m = vec![vec![Fp::zero(); 3]; 3];
+    m[0][0] = Fp::one();
+    m[1][2] = Fp::one();
+    m[2][1] = -Fp::one();
+    assert!(!is_secure_mds(&m));
+
+    // or a cyclic permutation, which fixes the all-one vector
+    let m: Vec<Vec<Fp>> = (0..3)
+        .map(|i| {
+            (0..3)
+                .map(|j| {
+                    if j == (i + 1) % 3 {
+                        Fp::one()
+                    } else {
+                        Fp::zero()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    assert!(!is_secure_mds(&m));
+}
+
+#[test]
+fn mds_determinant() {
+    for m in [fp::params().mds, fp5::params().mds, fp_3::params().mds].iter() {
+        assert!(!determinant(m).is_zero());
+    }
+
+    // the determinant of a matrix with a repeated row is zero, whichever row the pivot is
+    let mut m = fp::params().mds;
+    m[2] = m[0].clone();
+    assert!(determinant(&m).is_zero());
+    m.swap(0, 1);
+    assert!(determinant(&m).is_zero());
+
+    // and the rows swapped by the pivoting change the sign
+    let mut m = vec![vec![Fp::zero(); 3]; 3];
+    m[0][1] = Fp::one();
+    m[1][0] = Fp::one();
+    m[2][2] = Fp::one();
+    assert_eq!(determinant(&m), -Fp::one());
+}